@@ -10,7 +10,7 @@ use fui::fields::{Autocomplete, Checkbox, Multiselect};
 use fui::form::FormView;
 use fui::utils;
 use fui::validators::{DirExists, Required};
-use fui::{Fui, Value};
+use fui::{Fui, Map, Value};
 
 fn hdlr(v: Value) {
     println!("user input (from fn) {:?}", v);
@@ -32,7 +32,15 @@ fn main() {
                 .field(
                     Autocomplete::new("LINK_NAME", DirItems::new())
                         .help("Destiny of link")
-                        .validator(Required),
+                        .validator(Required)
+                        .context_validator(|data: &str, values: &Map<String, Value>| match values
+                            .get("TARGET")
+                        {
+                            Some(target) if target == data => {
+                                Err("Link can't point to itself".to_string())
+                            }
+                            _ => Ok(()),
+                        }),
                 )
                 .field(make_symbolic.clone().initial(true)),
             hdlr,
@@ -9,7 +9,7 @@ use fui::feeders::DirItems;
 use fui::fields::{Autocomplete, Multiselect};
 use fui::form::FormView;
 use fui::utils::cwd;
-use fui::validators::{FileExists, OneOf, PathFree, Required};
+use fui::validators::{FileExists, OneOf, ParentDirWritable, PathFree, Readable, Required, Warn};
 use fui::{Fui, Value};
 
 fn hdlr(v: Value) {
@@ -33,13 +33,15 @@ fn main() {
                     Multiselect::new("file-to-archive", DirItems::new())
                         .help("Files which should be archived")
                         .validator(Required)
-                        .validator(FileExists),
+                        .validator(FileExists)
+                        .validator(Readable),
                 )
                 .field(
                     Autocomplete::new("target", DirItems::dirs())
                         .help("Name of archive file")
                         .validator(Required)
-                        .validator(PathFree),
+                        .validator(Warn(PathFree))
+                        .validator(ParentDirWritable),
                 )
                 .field(compression.clone()),
             hdlr,
@@ -2,15 +2,46 @@
 extern crate cursive;
 extern crate fui;
 
+use std::path::Path;
 use std::rc::Rc;
 
 use cursive::traits::Boxable;
 use cursive::views::{Dialog, DummyView, LinearLayout};
 use cursive::Cursive;
 
-use fui::feeders::DirItems;
+use fui::feeders::matchers::{Fuzzy, SmartCase};
+use fui::feeders::system::{Hosts, Users};
+use fui::feeders::{Command, Cursor, DirItems, Feeder, FileItems, Items, Suggestion, Total};
 use fui::views::Autocomplete;
 
+// Suggests endless numbers starting with typed digits, read only as far as they're scrolled
+struct Numbers;
+
+impl Feeder for Numbers {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        self.cursor(text)
+            .map(|c| {
+                c.skip(position)
+                    .take(items_count)
+                    .map(|s| s.value)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn cursor(&self, text: &str) -> Option<Cursor> {
+        if !text.chars().all(|c| c.is_ascii_digit()) {
+            // nothing would be found ever
+            return Some(Cursor::empty());
+        }
+        let text = text.to_string();
+        let numbers = (0u64..)
+            .map(|x| x.to_string())
+            .filter(move |x| x.starts_with(&text));
+        Some(Cursor::new(numbers, Total::Unknown))
+    }
+}
+
 fn handler(c: &mut Cursive, submitted: Rc<String>) {
     let text = format!("submitted {:?}", submitted);
     c.add_layer(Dialog::info(text));
@@ -28,10 +59,76 @@ fn main() {
             .on_submit(handler),
         )
         .child(DummyView)
+        // matches like "cfg" -> "config.toml", best matches first
+        .child(
+            Autocomplete::new(
+                Items::new(vec![
+                    "Cargo.toml",
+                    "config.toml",
+                    "src/lib.rs",
+                    "src/feeders/matchers.rs",
+                ])
+                .matcher(SmartCase(Fuzzy)),
+            )
+            .on_submit(handler),
+        )
+        .child(DummyView)
+        // shows "gzip — fast, moderate ratio", inserts "gzip"
+        .child(
+            Autocomplete::new(Items::from_suggestions(vec![
+                Suggestion::new("gzip").description("fast, moderate ratio"),
+                Suggestion::new("bzip2").description("slower, better ratio"),
+                Suggestion::new("xz").description("slow, high ratio"),
+            ]))
+            .on_submit(handler),
+        )
+        .child(DummyView)
+        // suggests git branches (or shows why it can't)
+        .child(
+            Autocomplete::new(Command::new("git").args(&[
+                "branch",
+                "--all",
+                "--format=%(refname:short)",
+            ]))
+            .on_submit(handler),
+        )
+        .child(DummyView)
+        // lines of a file, reloaded when it changes
+        .child(Autocomplete::new(FileItems::lines("/etc/shells")).on_submit(handler))
+        .child(DummyView)
+        // local users & hosts (from /etc/hosts and ~/.ssh/config)
+        .child(Autocomplete::new(Users::new()).on_submit(handler))
+        .child(Autocomplete::new(Hosts::new()).on_submit(handler))
+        .child(DummyView)
+        .child(Autocomplete::new(Numbers).on_submit(handler))
+        .child(DummyView)
         .child(Autocomplete::new(DirItems::new()).on_submit(handler))
         .child(DummyView)
+        // dirs (ending with `/`) first, hidden files too, except ignored by git
+        .child(
+            Autocomplete::new(
+                DirItems::new()
+                    .show_hidden()
+                    .mark_dirs()
+                    .dirs_first()
+                    .respect_gitignore(),
+            )
+            .on_submit(handler),
+        )
+        .child(DummyView)
         // completes paths as absolute paths
         .child(Autocomplete::new(DirItems::new().use_full_paths()).on_submit(handler))
+        .child(DummyView)
+        // recent archives first, then only archives found on disk
+        .child(
+            Autocomplete::new(
+                DirItems::new()
+                    .filter(|s| s.value.ends_with(".tar.gz") || Path::new(&s.value).is_dir())
+                    .with_static_prefix(vec!["~/backups/latest.tar.gz"])
+                    .dedupe(),
+            )
+            .on_submit(handler),
+        )
         .child(DummyView);
 
     c.add_layer(Dialog::around(layout).full_width());
@@ -8,7 +8,8 @@ use cursive::views::Dialog;
 use cursive::Cursive;
 use serde_json::value::Value;
 
-use fui::fields::Autocomplete;
+use fui::feeders::git;
+use fui::fields::{Autocomplete, Text};
 use fui::form::FormView;
 use fui::validators::{OneOf, Required};
 
@@ -37,6 +38,16 @@ fn main() {
                 .initial("some text")
                 .validator(Required),
         )
+        // suggestions depend on other fields, repository is taken from field `repo`
+        .field(Text::new("repo").initial("."))
+        .field(Autocomplete::with_context(
+            "branch",
+            git::branches().repo_field("repo"),
+        ))
+        .field(Autocomplete::with_context(
+            "commit",
+            git::commits().repo_field("repo"),
+        ))
         .on_submit(show_data);
     siv.add_layer(Dialog::around(form).full_screen());
 
@@ -0,0 +1,36 @@
+// Demonstrates features of field Editor
+extern crate cursive;
+extern crate fui;
+extern crate serde_json;
+
+use cursive::traits::Boxable;
+use cursive::views::Dialog;
+use cursive::Cursive;
+use serde_json::value::Value;
+
+use fui::fields::Editor;
+use fui::form::FormView;
+use fui::validators::Required;
+
+fn show_data(c: &mut Cursive, data: Value) {
+    let text = format!("Got data: {:?}", data);
+    c.add_layer(Dialog::info(text));
+}
+
+fn main() {
+    let mut siv = Cursive::default();
+
+    let form = FormView::new()
+        .field(
+            Editor::new("config")
+                .help("opens $VISUAL or $EDITOR")
+                .extension("yaml")
+                .initial("name: fui\nitems:\n  - one\n  - two\n")
+                .validator(Required),
+        )
+        .field(Editor::new("message").help("commit message like"))
+        .on_submit(show_data);
+    siv.add_layer(Dialog::around(form).full_screen());
+
+    siv.run();
+}
@@ -0,0 +1,42 @@
+// Demonstrates features of field KeyValue
+extern crate cursive;
+extern crate fui;
+extern crate regex;
+extern crate serde_json;
+
+use cursive::traits::Boxable;
+use cursive::views::Dialog;
+use cursive::Cursive;
+use regex::Regex;
+use serde_json::value::Value;
+
+use fui::fields::KeyValue;
+use fui::form::FormView;
+use fui::validators::Required;
+
+fn show_data(c: &mut Cursive, data: Value) {
+    let text = format!("Got data: {:?}", data);
+    c.add_layer(Dialog::info(text));
+}
+
+fn main() {
+    let mut siv = Cursive::default();
+
+    let form = FormView::new()
+        .field(KeyValue::new("env").help("dumped as: --env KEY=VALUE"))
+        .field(
+            KeyValue::new("define")
+                .help("value is JSON object")
+                .initial(vec![("DEBUG", "1")])
+                .as_object(),
+        )
+        .field(
+            KeyValue::new("label")
+                .key_validator(Regex::new("^[a-z.]+$").unwrap())
+                .value_validator(Required),
+        )
+        .on_submit(show_data);
+    siv.add_layer(Dialog::around(form).fixed_width(60));
+
+    siv.run();
+}
@@ -0,0 +1,47 @@
+// Demonstrates features of field Path
+extern crate cursive;
+extern crate fui;
+extern crate serde_json;
+
+use cursive::traits::Boxable;
+use cursive::views::Dialog;
+use cursive::Cursive;
+use serde_json::value::Value;
+
+use fui::fields::Path;
+use fui::form::FormView;
+use fui::validators::{DirExists, FileExists, PathFree};
+
+fn show_data(c: &mut Cursive, data: Value) {
+    let text = format!("Got data: {:?}", data);
+    c.add_layer(Dialog::info(text));
+}
+
+fn main() {
+    let mut siv = Cursive::default();
+
+    let form = FormView::new()
+        .field(
+            Path::new("manifest")
+                .help("only *.toml files")
+                .extensions(vec!["toml"])
+                .validator(FileExists),
+        )
+        .field(
+            Path::new("target-dir")
+                .help("only dirs")
+                .dirs_only()
+                .validator(DirExists),
+        )
+        .field(Path::new("output").help("new path").validator(PathFree))
+        .field(
+            Path::multiple("sources")
+                .help("many files, including hidden ones")
+                .show_hidden()
+                .root("./src"),
+        )
+        .on_submit(show_data);
+    siv.add_layer(Dialog::around(form).full_screen());
+
+    siv.run();
+}
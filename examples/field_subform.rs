@@ -0,0 +1,41 @@
+// Demonstrates features of field SubForm
+extern crate cursive;
+extern crate fui;
+extern crate serde_json;
+
+use cursive::traits::Boxable;
+use cursive::views::Dialog;
+use cursive::Cursive;
+use serde_json::value::Value;
+
+use fui::fields::{Autocomplete, Checkbox, SubForm, Text};
+use fui::form::FormView;
+use fui::validators::Required;
+
+fn show_data(c: &mut Cursive, data: Value) {
+    let text = format!("Got data: {:?}", data);
+    c.add_layer(Dialog::info(text));
+}
+
+fn main() {
+    let mut siv = Cursive::default();
+
+    let form = FormView::new()
+        .field(
+            SubForm::new("database")
+                .help("single record")
+                .field(Text::new("host").initial("localhost").validator(Required))
+                .field(Checkbox::new("ssl")),
+        )
+        .field(
+            SubForm::repeatable("port")
+                .help("list of records")
+                .field(Text::new("host").validator(Required))
+                .field(Text::new("container").validator(Required))
+                .field(Autocomplete::new("protocol", vec!["tcp", "udp"]).initial("tcp")),
+        )
+        .on_submit(show_data);
+    siv.add_layer(Dialog::around(form).fixed_width(70));
+
+    siv.run();
+}
@@ -10,6 +10,7 @@ use cursive::Cursive;
 use regex::Regex;
 use serde_json::value::Value;
 
+use fui::converters::{ToInt, ToList, Trim};
 use fui::fields::Text;
 use fui::form::FormView;
 use fui::validators::Required;
@@ -32,6 +33,18 @@ fn main() {
                 .validator(Required)
                 .validator(Regex::new("[0-9]").unwrap()),
         )
+        .field(
+            Text::new("with-converter")
+                .help("integer")
+                .converter(Trim)
+                .converter(ToInt),
+        )
+        .field(
+            Text::new("with-converters")
+                .help("comma separated list")
+                .converter(ToList(","))
+                .validator(Required),
+        )
         .field(
             Text::new("all-in-one")
                 .help("help")
@@ -0,0 +1,34 @@
+// Demonstrates features of field Toggle
+extern crate cursive;
+extern crate fui;
+extern crate serde_json;
+
+use cursive::views::Dialog;
+use cursive::Cursive;
+use serde_json::value::Value;
+
+use fui::fields::Toggle;
+use fui::form::FormView;
+
+fn show_data(c: &mut Cursive, data: Value) {
+    let text = format!("Got data: {:?}", data);
+    c.add_layer(Dialog::info(text));
+}
+
+fn main() {
+    let mut siv = Cursive::default();
+
+    let form = FormView::new()
+        .field(Toggle::new("color").help("--color / --no-color"))
+        .field(Toggle::new("cache").initial(Some(false)))
+        .field(
+            Toggle::new("verbose")
+                .negative("quiet")
+                .help("--verbose / --quiet")
+                .initial(Some(true)),
+        )
+        .on_submit(show_data);
+    siv.add_layer(Dialog::around(form));
+
+    siv.run();
+}
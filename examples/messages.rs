@@ -0,0 +1,37 @@
+// Demonstrates translating texts shown to user
+extern crate fui;
+
+use fui::fields::Text;
+use fui::form::FormView;
+use fui::messages::Table;
+use fui::validators::{MinLength, Required};
+use fui::Fui;
+
+fn main() {
+    let polish = Table::from_json(
+        r#"{
+            "form.cancel": "Anuluj",
+            "form.submit": "Zatwierdź (Ctrl+f)",
+            "fui.pick_action": "Wybierz akcję",
+            "validators.required": "Pole jest wymagane",
+            "validators.min_length": "Wartość musi mieć co najmniej {min} znaki"
+        }"#,
+    )
+    .unwrap();
+
+    Fui::new("messages")
+        .messages(polish)
+        .action(
+            "greet",
+            "Przywitaj się",
+            FormView::new().field(
+                Text::new("name")
+                    .validator(Required)
+                    .validator(MinLength(3)),
+            ),
+            |v| {
+                println!("user input (from callback) {:?}", v);
+            },
+        )
+        .run();
+}
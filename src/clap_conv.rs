@@ -6,8 +6,9 @@ use clap;
 use clap::ArgSettings;
 use feeders::DirItems;
 use fields::autocomplete::AutocompleteManager;
+use fields::keyvalue::KeyValueManager;
 use fields::multiselect::MultiselectManager;
-use fields::{Autocomplete, Checkbox, Field, FormField, Text};
+use fields::{Autocomplete, Checkbox, Field, FormField, KeyValue, Text, Toggle};
 use form::FormView;
 use std::ffi::OsStr;
 use std::rc::Rc;
@@ -66,6 +67,33 @@ fn copy_default_multi(
     field
 }
 
+/// Copies multi `default` value of `KEY=VALUE` pairs to `field`.
+fn copy_default_pairs(
+    mut field: Field<KeyValueManager, Vec<(String, String)>>,
+    default: Option<&OsStr>,
+    delimeter: Option<char>,
+) -> Field<KeyValueManager, Vec<(String, String)>> {
+    if let Some(v) = default {
+        if let Some(s) = v.to_str() {
+            let delimeter = delimeter.unwrap_or(' ');
+            let pairs = split_values(s, delimeter)
+                .iter()
+                .filter_map(|pair| {
+                    pair.find('=')
+                        .map(|idx| (pair[..idx].to_string(), pair[idx + 1..].to_string()))
+                })
+                .collect::<Vec<(String, String)>>();
+            field = field.initial(pairs);
+        }
+    }
+    field
+}
+
+/// Checks if any of `val_names` looks like `KEY=VALUE`.
+fn is_key_value(val_names: &[&str]) -> bool {
+    val_names.iter().any(|name| name.contains('='))
+}
+
 /// Gets field depends on `values`.
 fn field_with_vals<V: Into<String>>(
     values: &Option<Vec<&str>>,
@@ -125,6 +153,41 @@ macro_rules! warn_incompat {
     )
 }
 
+/// Finds flags overriding each other (like `--color` & `--no-color`).
+///
+/// Returns pairs of indexes (positive flag, negative flag).
+fn find_flag_pairs(clap_app: &clap::App) -> Vec<(usize, usize)> {
+    let flags = &clap_app.p.flags;
+    let overrides = |a: usize, b: usize| {
+        flags[a]
+            .b
+            .overrides
+            .as_ref()
+            .map(|list| list.contains(&flags[b].b.name))
+            .unwrap_or(false)
+    };
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+    for a in 0..flags.len() {
+        for b in (a + 1)..flags.len() {
+            let paired = pairs
+                .iter()
+                .any(|&(p, n)| p == a || n == a || p == b || n == b);
+            let multiple = flags[a].b.settings.is_set(ArgSettings::Multiple)
+                || flags[b].b.settings.is_set(ArgSettings::Multiple);
+            if paired || multiple || !(overrides(a, b) || overrides(b, a)) {
+                continue;
+            }
+            // negative flag is the one named like `no-<positive>`
+            let pair = match (flags[a].s.long, flags[b].s.long) {
+                (Some(long_a), Some(long_b)) if long_a == format!("no-{}", long_b) => (b, a),
+                _ => (a, b),
+            };
+            pairs.push(pair);
+        }
+    }
+    pairs
+}
+
 fn clap_app2fields(clap_app: &clap::App) -> Vec<Box<FormField>> {
     let mut field_list = Vec::new();
     // TODO: flag & option & positional loops are mostly copy & paste so make it DRY
@@ -169,7 +232,20 @@ fn clap_app2fields(clap_app: &clap::App) -> Vec<Box<FormField>> {
             .b
             .help
             .expect(&format!("Arg {:?} must have help", option.b.name));
-        if option.b.settings.is_set(ArgSettings::Multiple) {
+        let val_names = option
+            .v
+            .val_names
+            .as_ref()
+            .map(|names| names.values().cloned().collect::<Vec<&str>>())
+            .unwrap_or(Vec::new());
+        if option.b.settings.is_set(ArgSettings::Multiple) && is_key_value(&val_names) {
+            let mut field = KeyValue::new(long).help(help);
+            if option.b.settings.is_set(ArgSettings::Required) {
+                field = field.validator(Required);
+            }
+            field = copy_default_pairs(field, option.v.default_val, option.v.val_delim);
+            field_list.push(Box::new(field) as Box<dyn FormField>);
+        } else if option.b.settings.is_set(ArgSettings::Multiple) {
             let mut field = field_multi_with_vals(&option.v.possible_vals, long, help);
             if option.b.settings.is_set(ArgSettings::Required) {
                 field = field.validator(Required);
@@ -185,8 +261,13 @@ fn clap_app2fields(clap_app: &clap::App) -> Vec<Box<FormField>> {
             field_list.push(Box::new(field) as Box<FormField>);
         }
     }
-    for flag in clap_app.p.flags.iter() {
+    let flag_pairs = find_flag_pairs(clap_app);
+    for (idx, flag) in clap_app.p.flags.iter().enumerate() {
         //println!("FLAG {:?}\n", flag.b);
+        if flag_pairs.iter().any(|&(_, negative)| negative == idx) {
+            // it's already covered by `Toggle` of the positive flag
+            continue;
+        }
         warn_incompat!(flag.b);
         // TODO: improve by allowing short + help?
         let long = flag
@@ -197,7 +278,18 @@ fn clap_app2fields(clap_app: &clap::App) -> Vec<Box<FormField>> {
             .b
             .help
             .expect(&format!("Arg {:?} must have help", flag.b.name));
-        if flag.b.settings.is_set(ArgSettings::Multiple) {
+        if let Some(&(_, negative)) = flag_pairs.iter().find(|&&(positive, _)| positive == idx) {
+            let negative = &clap_app.p.flags[negative];
+            let negative_long = negative
+                .s
+                .long
+                .unwrap_or_else(|| panic!("Arg {:?} must have long name", negative.b.name));
+            let field = Toggle::new(long)
+                .help(help)
+                .negative(negative_long)
+                .negative_help(negative.b.help.unwrap_or(""));
+            field_list.push(Box::new(field) as Box<dyn FormField>);
+        } else if flag.b.settings.is_set(ArgSettings::Multiple) {
             // TODO: add validator for a positive integer
             let field = Text::new(long).help(help);
             field_list.push(Box::new(field) as Box<FormField>);
@@ -386,6 +478,72 @@ mod switches {
     }
 }
 
+#[cfg(test)]
+mod flag_pairs {
+    use super::*;
+    use Action;
+
+    fn color_app<'a, 'b>() -> clap::App<'a, 'b> {
+        clap::App::new("virtua_fighter")
+            .arg(
+                clap::Arg::with_name("no-color")
+                    .long("no-color")
+                    .help("disable colors")
+                    .overrides_with("color"),
+            )
+            .arg(
+                clap::Arg::with_name("color")
+                    .long("color")
+                    .help("enable colors")
+                    .overrides_with("no-color"),
+            )
+    }
+
+    #[test]
+    fn overriding_flags_are_converted_to_single_toggle() {
+        let app = color_app();
+        let fui = Fui::from(&app);
+        let action: &Action = fui
+            .action_by_name("virtua_fighter")
+            .expect("expected default action");
+
+        let fields = action.form.as_ref().unwrap().get_fields();
+
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].get_label(), "color");
+        assert_eq!(fields[0].get_help(), "enable colors");
+    }
+
+    #[test]
+    fn toggle_is_dumped_as_negative_flag() {
+        let app = color_app();
+        let fui = Fui::from(&app);
+        let action: &Action = fui
+            .action_by_name("virtua_fighter")
+            .expect("expected default action");
+        let form = action.form.as_ref().unwrap();
+
+        let dumped = form.dump_as_cli(&serde_json::from_str(r#"{ "color": false }"#).unwrap());
+
+        assert_eq!(dumped, vec!["--no-color"]);
+    }
+
+    #[test]
+    fn not_overriding_flags_are_converted_to_checkboxes() {
+        let app = clap::App::new("virtua_fighter")
+            .arg(clap::Arg::with_name("a").long("a").help("a"))
+            .arg(clap::Arg::with_name("no-a").long("no-a").help("no-a"));
+        let fui = Fui::from(&app);
+        let action: &Action = fui
+            .action_by_name("virtua_fighter")
+            .expect("expected default action");
+
+        let fields = action.form.as_ref().unwrap().get_fields();
+
+        assert_eq!(fields.len(), 2);
+    }
+}
+
 #[cfg(test)]
 mod option_args {
     use super::*;
@@ -484,6 +642,51 @@ mod option_args {
     }
 }
 
+#[cfg(test)]
+mod key_value_args {
+    use super::*;
+    use Action;
+
+    fn env_app<'a, 'b>() -> clap::App<'a, 'b> {
+        clap::App::new("virtua_fighter").arg(
+            clap::Arg::with_name("env")
+                .long("env")
+                .help("help")
+                .value_name("KEY=VALUE")
+                .multiple(true)
+                .number_of_values(1)
+                .default_value("A=1"),
+        )
+    }
+
+    #[test]
+    fn key_value_option_uses_default_value_if_present() {
+        let app = env_app();
+        let fui = Fui::from(&app);
+        let action: &Action = fui
+            .action_by_name("virtua_fighter")
+            .expect("expected default action");
+
+        let initial = action.form.as_ref().unwrap().get_field_value("env");
+        assert_eq!(initial, Some("A=1".to_string()));
+    }
+
+    #[test]
+    fn key_value_option_is_dumped_as_repeated_option() {
+        let app = env_app();
+        let fui = Fui::from(&app);
+        let action: &Action = fui
+            .action_by_name("virtua_fighter")
+            .expect("expected default action");
+        let form = action.form.as_ref().unwrap();
+
+        let dumped =
+            form.dump_as_cli(&serde_json::from_str(r#"{ "env": ["A=1", "B=2"] }"#).unwrap());
+
+        assert_eq!(dumped, vec!["--env", "A=1", "--env", "B=2"]);
+    }
+}
+
 #[cfg(test)]
 mod positional_args {
     use super::*;
@@ -591,7 +794,6 @@ mod positional_args {
         let initial = action.form.as_ref().unwrap().get_field_value("0");
         assert_eq!(initial, Some("default".to_string()));
     }
-
 }
 
 #[cfg(test)]
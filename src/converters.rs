@@ -0,0 +1,483 @@
+//! Provides data converters used by `Fields`.
+//!
+//! Converters run before validators, in order of adding them to a field.
+//! Normalizers (like [Trim]) change text, parsers (like [ToInt]) change its type.
+//!
+//! [Trim]: ./struct.Trim.html
+//! [ToInt]: ./struct.ToInt.html
+use regex::{Captures, Regex};
+use serde_json::value::Value;
+use std::env;
+use std::fmt::Debug;
+use std::fs;
+use std::path::Path;
+
+use dirs;
+use messages::Message;
+use validators::ValidationError;
+
+/// Adds behaviour of conversion.
+pub trait Converter: Debug {
+    /// Converts `value` returning converted one or `ValidationError`.
+    fn convert(&self, value: Value) -> Result<Value, ValidationError>;
+    /// Converts `value` back to a form accepted by `convert`.
+    ///
+    /// It's used to dump converted value to CLI, so parsers must implement it.
+    fn revert(&self, value: Value) -> Value {
+        value
+    }
+}
+
+/// Applies `f` to string `value` (or to each string of array).
+fn map_str(
+    value: Value,
+    f: &dyn Fn(&str) -> Result<String, ValidationError>,
+) -> Result<Value, ValidationError> {
+    match value {
+        Value::String(s) => f(&s).map(Value::String),
+        Value::Array(items) => {
+            let mut converted = Vec::with_capacity(items.len());
+            for item in items.into_iter() {
+                converted.push(map_str(item, f)?);
+            }
+            Ok(Value::Array(converted))
+        }
+        other => Ok(other),
+    }
+}
+
+/// Applies parser `f` to string `value`, empty string is converted to `Null`.
+fn parse_str<F>(value: Value, f: F) -> Result<Value, ValidationError>
+where
+    F: Fn(&str) -> Result<Value, ValidationError>,
+{
+    match value {
+        Value::String(ref s) if s.is_empty() => Ok(Value::Null),
+        Value::String(s) => f(&s),
+        other => Ok(other),
+    }
+}
+
+/// Message with `data` as parameter `value`.
+fn with_value(key: &str, data: &str) -> Message {
+    Message::new(key).param("value", format!("{:?}", data))
+}
+
+/// Converts value to its text form (`Null` becomes an empty string).
+fn value2str(value: Value) -> Value {
+    match value {
+        Value::Null => Value::String("".to_string()),
+        Value::String(s) => Value::String(s),
+        other => Value::String(other.to_string()),
+    }
+}
+
+/// Removes leading and trailing whitespace.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate serde_json;
+/// # extern crate fui;
+/// use fui::converters::{Converter, Trim};
+///
+/// # fn main() {
+/// assert_eq!(Trim.convert(json!("  some data\n")), Ok(json!("some data")));
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Trim;
+
+impl Converter for Trim {
+    fn convert(&self, value: Value) -> Result<Value, ValidationError> {
+        map_str(value, &|s| Ok(s.trim().to_string()))
+    }
+}
+
+/// Converts text to lowercase.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate serde_json;
+/// # extern crate fui;
+/// use fui::converters::{Converter, Lowercase};
+///
+/// # fn main() {
+/// assert_eq!(Lowercase.convert(json!("Debug")), Ok(json!("debug")));
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Lowercase;
+
+impl Converter for Lowercase {
+    fn convert(&self, value: Value) -> Result<Value, ValidationError> {
+        map_str(value, &|s| Ok(s.to_lowercase()))
+    }
+}
+
+/// Replaces leading `~` with user's home dir.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate serde_json;
+/// # extern crate fui;
+/// use fui::converters::{Converter, ExpandUser};
+/// use fui::utils::home_dir;
+///
+/// # fn main() {
+/// let exp = format!("{}/.bashrc", home_dir());
+/// assert_eq!(ExpandUser.convert(json!("~/.bashrc")), Ok(json!(exp)));
+/// assert_eq!(ExpandUser.convert(json!("./~")), Ok(json!("./~")));
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ExpandUser;
+
+impl Converter for ExpandUser {
+    fn convert(&self, value: Value) -> Result<Value, ValidationError> {
+        map_str(value, &|s| {
+            if s != "~" && !s.starts_with("~/") {
+                return Ok(s.to_string());
+            }
+            match dirs::home_dir() {
+                Some(home) => Ok(format!("{}{}", home.display(), &s[1..])),
+                None => Err(Message::new("converters.home_unknown").into()),
+            }
+        })
+    }
+}
+
+/// Replaces `$VAR` and `${VAR}` with values of environment variables.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate serde_json;
+/// # extern crate fui;
+/// use fui::converters::{Converter, ExpandVars};
+///
+/// # fn main() {
+/// std::env::set_var("FUI_DIR", "/tmp");
+/// assert_eq!(ExpandVars.convert(json!("${FUI_DIR}/x")), Ok(json!("/tmp/x")));
+/// assert_eq!(
+///     ExpandVars.convert(json!("$FUI_MISSING")).unwrap_err(),
+///     "Variable \"FUI_MISSING\" is not set"
+/// );
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ExpandVars;
+
+thread_local! {
+    // Variable like `$NAME` or `${NAME}`
+    static VAR: Regex =
+        Regex::new(r"\$(?:([A-Za-z_][A-Za-z0-9_]*)|\{([A-Za-z_][A-Za-z0-9_]*)\})").unwrap();
+}
+
+impl Converter for ExpandVars {
+    fn convert(&self, value: Value) -> Result<Value, ValidationError> {
+        VAR.with(|re| {
+            map_str(value, &|s| {
+                for caps in re.captures_iter(s) {
+                    let name = caps.get(1).or(caps.get(2)).unwrap().as_str();
+                    if env::var(name).is_err() {
+                        let msg = Message::new("converters.var_missing")
+                            .param("name", format!("{:?}", name));
+                        return Err(msg.into());
+                    }
+                }
+                let expanded = re.replace_all(s, |caps: &Captures| {
+                    let name = caps.get(1).or(caps.get(2)).unwrap().as_str();
+                    env::var(name).unwrap_or("".to_string())
+                });
+                Ok(expanded.into_owned())
+            })
+        })
+    }
+}
+
+/// Makes path absolute (relative to current dir).
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate serde_json;
+/// # extern crate fui;
+/// use fui::converters::{Absolute, Converter};
+/// use fui::utils::cwd;
+///
+/// # fn main() {
+/// let exp = format!("{}/src", cwd());
+/// assert_eq!(Absolute.convert(json!("src")), Ok(json!(exp)));
+/// assert_eq!(Absolute.convert(json!("/tmp")), Ok(json!("/tmp")));
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Absolute;
+
+impl Converter for Absolute {
+    fn convert(&self, value: Value) -> Result<Value, ValidationError> {
+        map_str(value, &|s| {
+            let path = Path::new(s);
+            if s.is_empty() || path.is_absolute() {
+                return Ok(s.to_string());
+            }
+            env::current_dir()
+                .map(|cwd| format!("{}", cwd.join(path).display()))
+                .map_err(|e| {
+                    Message::new("converters.cwd_unknown")
+                        .param("error", e)
+                        .into()
+                })
+        })
+    }
+}
+
+/// Makes path canonical (absolute, without `..` and symlinks), path must exist.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate serde_json;
+/// # extern crate fui;
+/// use fui::converters::{Canonical, Converter};
+/// use fui::utils::cwd;
+///
+/// # fn main() {
+/// let exp = format!("{}/src", cwd());
+/// assert_eq!(Canonical.convert(json!("./src/../src")), Ok(json!(exp)));
+/// assert!(Canonical.convert(json!("./missing-path")).is_err());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Canonical;
+
+impl Converter for Canonical {
+    fn convert(&self, value: Value) -> Result<Value, ValidationError> {
+        map_str(value, &|s| {
+            if s.is_empty() {
+                return Ok(s.to_string());
+            }
+            fs::canonicalize(s)
+                .map(|p| format!("{}", p.display()))
+                .map_err(|e| {
+                    let msg = with_value("converters.unresolved_path", s).param("error", e);
+                    msg.into()
+                })
+        })
+    }
+}
+
+/// Parses text to integer number.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate serde_json;
+/// # extern crate fui;
+/// use fui::converters::{Converter, ToInt};
+///
+/// # fn main() {
+/// assert_eq!(ToInt.convert(json!("-12")), Ok(json!(-12)));
+/// assert_eq!(ToInt.convert(json!("")), Ok(json!(null)));
+/// let err = ToInt.convert(json!("1.5")).unwrap_err();
+/// assert_eq!(err.code, "converters.not_integer");
+/// assert_eq!(err.message, "Value \"1.5\" is not an integer");
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ToInt;
+
+impl Converter for ToInt {
+    fn convert(&self, value: Value) -> Result<Value, ValidationError> {
+        parse_str(value, |s| {
+            s.parse::<i64>()
+                .map(Value::from)
+                .map_err(|_| with_value("converters.not_integer", s).into())
+        })
+    }
+    fn revert(&self, value: Value) -> Value {
+        value2str(value)
+    }
+}
+
+/// Parses text to float number.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate serde_json;
+/// # extern crate fui;
+/// use fui::converters::{Converter, ToFloat};
+///
+/// # fn main() {
+/// assert_eq!(ToFloat.convert(json!("0.5")), Ok(json!(0.5)));
+/// assert_eq!(ToFloat.convert(json!("x")).unwrap_err(), "Value \"x\" is not a number");
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ToFloat;
+
+impl Converter for ToFloat {
+    fn convert(&self, value: Value) -> Result<Value, ValidationError> {
+        parse_str(value, |s| {
+            let number = s.parse::<f64>().ok().and_then(|n| {
+                // NaN & infinity aren't valid JSON numbers
+                ::serde_json::Number::from_f64(n)
+            });
+            number
+                .map(Value::Number)
+                .ok_or(with_value("converters.not_number", s).into())
+        })
+    }
+    fn revert(&self, value: Value) -> Value {
+        value2str(value)
+    }
+}
+
+/// Parses text to boolean (`true`/`false`, `yes`/`no`, `on`/`off`, `1`/`0`).
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate serde_json;
+/// # extern crate fui;
+/// use fui::converters::{Converter, ToBool};
+///
+/// # fn main() {
+/// assert_eq!(ToBool.convert(json!("Yes")), Ok(json!(true)));
+/// assert_eq!(ToBool.convert(json!("off")), Ok(json!(false)));
+/// assert_eq!(ToBool.convert(json!("x")).unwrap_err(), "Value \"x\" is not a boolean");
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ToBool;
+
+impl Converter for ToBool {
+    fn convert(&self, value: Value) -> Result<Value, ValidationError> {
+        parse_str(value, |s| match s.to_lowercase().as_str() {
+            "true" | "yes" | "on" | "1" => Ok(Value::Bool(true)),
+            "false" | "no" | "off" | "0" => Ok(Value::Bool(false)),
+            _ => Err(with_value("converters.not_bool", s).into()),
+        })
+    }
+    fn revert(&self, value: Value) -> Value {
+        value2str(value)
+    }
+}
+
+/// Splits text to list by separator (items are trimmed, empty ones are skipped).
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate serde_json;
+/// # extern crate fui;
+/// use fui::converters::{Converter, ToList};
+///
+/// # fn main() {
+/// assert_eq!(ToList(",").convert(json!("a, b,,c")), Ok(json!(["a", "b", "c"])));
+/// assert_eq!(ToList(",").convert(json!("")), Ok(json!([])));
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ToList(pub &'static str);
+
+impl Converter for ToList {
+    fn convert(&self, value: Value) -> Result<Value, ValidationError> {
+        match value {
+            Value::String(s) => {
+                let items = s
+                    .split(self.0)
+                    .map(|x| x.trim())
+                    .filter(|x| !x.is_empty())
+                    .map(|x| Value::String(x.to_string()))
+                    .collect();
+                Ok(Value::Array(items))
+            }
+            other => Ok(other),
+        }
+    }
+    fn revert(&self, value: Value) -> Value {
+        match value {
+            Value::Array(items) => {
+                let items = items
+                    .into_iter()
+                    .map(|x| match value2str(x) {
+                        Value::String(s) => s,
+                        _ => unreachable!(),
+                    })
+                    .collect::<Vec<String>>();
+                Value::String(items.join(self.0))
+            }
+            other => other,
+        }
+    }
+}
+
+/// Parses duration like `90`, `90s`, `1m30s`, `2h`, `1d` to number of seconds.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate serde_json;
+/// # extern crate fui;
+/// use fui::converters::{Converter, ToDuration};
+///
+/// # fn main() {
+/// assert_eq!(ToDuration.convert(json!("1h30m")), Ok(json!(5400)));
+/// assert_eq!(ToDuration.convert(json!("45")), Ok(json!(45)));
+/// assert_eq!(
+///     ToDuration.convert(json!("1x")).unwrap_err(),
+///     "Value \"1x\" is not a duration (like 1d2h3m4s)"
+/// );
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ToDuration;
+
+impl Converter for ToDuration {
+    fn convert(&self, value: Value) -> Result<Value, ValidationError> {
+        parse_str(value, |s| {
+            parse_duration(s)
+                .map(Value::from)
+                .ok_or(with_value("converters.not_duration", s).into())
+        })
+    }
+    fn revert(&self, value: Value) -> Value {
+        match value {
+            Value::Number(n) => Value::String(format!("{}s", n)),
+            other => value2str(other),
+        }
+    }
+}
+
+fn parse_duration(text: &str) -> Option<u64> {
+    if let Ok(secs) = text.parse::<u64>() {
+        return Some(secs);
+    }
+    let mut total = 0u64;
+    let mut number = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c {
+            'd' => 24 * 60 * 60,
+            'h' => 60 * 60,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        let n = number.parse::<u64>().ok()?;
+        total = total.checked_add(n.checked_mul(unit)?)?;
+        number.clear();
+    }
+    if !number.is_empty() {
+        return None;
+    }
+    Some(total)
+}
@@ -0,0 +1,452 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::iter;
+use std::rc::Rc;
+
+use super::{Cursor, Feeder, Suggestion, Total};
+
+/// Returns `items_count` suggestions of `cursor` starting at `position`.
+fn page(cursor: Cursor, position: usize, items_count: usize) -> Vec<Suggestion> {
+    cursor.skip(position).take(items_count).collect()
+}
+
+/// Returns values of `suggestions`.
+fn values(suggestions: Vec<Suggestion>) -> Vec<String> {
+    suggestions.into_iter().map(|s| s.value).collect()
+}
+
+/// Suggests items of the first feeder followed by items of the second one.
+///
+/// ```
+/// # extern crate fui;
+/// # use fui::feeders::{chain, Feeder};
+/// # fn main() {
+/// let recent = vec!["./src", "./examples"];
+/// let feeder = chain(recent, vec!["./src", "./tests"]);
+/// assert_eq!(feeder.query("", 1, 2), vec!["./examples", "./src"]);
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Chain {
+    first: Rc<dyn Feeder>,
+    second: Rc<dyn Feeder>,
+}
+
+/// Creates a new [Chain] suggesting items of `first` and then of `second`.
+///
+/// [Chain]: ./struct.Chain.html
+pub fn chain<A: Feeder, B: Feeder>(first: A, second: B) -> Chain {
+    Chain {
+        first: Rc::new(first),
+        second: Rc::new(second),
+    }
+}
+
+impl Feeder for Chain {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        values(self.suggest(text, position, items_count))
+    }
+
+    fn suggest(&self, text: &str, position: usize, items_count: usize) -> Vec<Suggestion> {
+        page(self.cursor(text).unwrap(), position, items_count)
+    }
+
+    /// Second feeder is queried after all items of the first one are read,
+    /// so total count is unknown.
+    fn cursor(&self, text: &str) -> Option<Cursor> {
+        let first = Cursor::open(&self.first, text);
+        let second = Rc::clone(&self.second);
+        let text = text.to_string();
+        let rest = iter::once(()).flat_map(move |_| Cursor::open(&second, &text));
+        Some(Cursor::new(first.chain(rest), Total::Unknown))
+    }
+
+    fn highlight(&self, text: &str, item: &str) -> Vec<usize> {
+        let found = self.first.highlight(text, item);
+        if found.is_empty() {
+            self.second.highlight(text, item)
+        } else {
+            found
+        }
+    }
+
+    fn error(&self, text: &str) -> Option<String> {
+        self.first.error(text).or_else(|| self.second.error(text))
+    }
+}
+
+/// Changes suggestions of a feeder (see [Feeder::map]).
+///
+/// [Feeder::map]: ./trait.Feeder.html#method.map
+#[derive(Clone)]
+pub struct Map {
+    feeder: Rc<dyn Feeder>,
+    map: Rc<dyn Fn(Suggestion) -> Suggestion>,
+}
+
+impl Map {
+    pub(crate) fn new<F, T>(feeder: Rc<dyn Feeder>, map: F) -> Self
+    where
+        F: Fn(Suggestion) -> T + 'static,
+        T: Into<Suggestion>,
+    {
+        Map {
+            feeder,
+            map: Rc::new(move |s| map(s).into()),
+        }
+    }
+}
+
+impl Feeder for Map {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        values(self.suggest(text, position, items_count))
+    }
+
+    fn suggest(&self, text: &str, position: usize, items_count: usize) -> Vec<Suggestion> {
+        page(self.cursor(text).unwrap(), position, items_count)
+    }
+
+    fn cursor(&self, text: &str) -> Option<Cursor> {
+        let cursor = Cursor::open(&self.feeder, text);
+        let total = cursor.total();
+        let map = Rc::clone(&self.map);
+        Some(Cursor::new(cursor.map(move |s| map(s)), total))
+    }
+
+    /// Positions are found in display of suggestion before it was changed.
+    fn highlight(&self, text: &str, item: &str) -> Vec<usize> {
+        self.feeder.highlight(text, item)
+    }
+
+    fn error(&self, text: &str) -> Option<String> {
+        self.feeder.error(text)
+    }
+}
+
+/// Suggests only items of a feeder passing a predicate (see [Feeder::filter]).
+///
+/// [Feeder::filter]: ./trait.Feeder.html#method.filter
+#[derive(Clone)]
+pub struct Filter {
+    feeder: Rc<dyn Feeder>,
+    predicate: Rc<dyn Fn(&Suggestion) -> bool>,
+}
+
+impl Filter {
+    pub(crate) fn new<F>(feeder: Rc<dyn Feeder>, predicate: F) -> Self
+    where
+        F: Fn(&Suggestion) -> bool + 'static,
+    {
+        Filter {
+            feeder,
+            predicate: Rc::new(predicate),
+        }
+    }
+}
+
+impl Feeder for Filter {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        values(self.suggest(text, position, items_count))
+    }
+
+    fn suggest(&self, text: &str, position: usize, items_count: usize) -> Vec<Suggestion> {
+        page(self.cursor(text).unwrap(), position, items_count)
+    }
+
+    /// Total count of items is unknown until all of them are read.
+    fn cursor(&self, text: &str) -> Option<Cursor> {
+        let predicate = Rc::clone(&self.predicate);
+        let cursor = Cursor::open(&self.feeder, text).filter(move |s| predicate(s));
+        Some(Cursor::new(cursor, Total::Unknown))
+    }
+
+    fn highlight(&self, text: &str, item: &str) -> Vec<usize> {
+        self.feeder.highlight(text, item)
+    }
+
+    fn error(&self, text: &str) -> Option<String> {
+        self.feeder.error(text)
+    }
+}
+
+/// Suggests items of a feeder skipping repeated values (see [Feeder::dedupe]).
+///
+/// [Feeder::dedupe]: ./trait.Feeder.html#method.dedupe
+#[derive(Clone)]
+pub struct Dedupe {
+    feeder: Rc<dyn Feeder>,
+}
+
+impl Dedupe {
+    pub(crate) fn new(feeder: Rc<dyn Feeder>) -> Self {
+        Dedupe { feeder }
+    }
+}
+
+impl Feeder for Dedupe {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        values(self.suggest(text, position, items_count))
+    }
+
+    fn suggest(&self, text: &str, position: usize, items_count: usize) -> Vec<Suggestion> {
+        page(self.cursor(text).unwrap(), position, items_count)
+    }
+
+    /// The first suggestion of each value is kept, total count of items
+    /// is unknown until all of them are read.
+    fn cursor(&self, text: &str) -> Option<Cursor> {
+        let mut seen = HashSet::new();
+        let cursor = Cursor::open(&self.feeder, text).filter(move |s| seen.insert(s.value.clone()));
+        Some(Cursor::new(cursor, Total::Unknown))
+    }
+
+    fn highlight(&self, text: &str, item: &str) -> Vec<usize> {
+        self.feeder.highlight(text, item)
+    }
+
+    fn error(&self, text: &str) -> Option<String> {
+        self.feeder.error(text)
+    }
+}
+
+/// Compares suggestions to sort them.
+type Compare = Rc<dyn Fn(&Suggestion, &Suggestion) -> Ordering>;
+
+/// Suggests items of a feeder in a different order (see [Feeder::sorted_by]).
+///
+/// [Feeder::sorted_by]: ./trait.Feeder.html#method.sorted_by
+#[derive(Clone)]
+pub struct SortedBy {
+    feeder: Rc<dyn Feeder>,
+    compare: Compare,
+}
+
+impl SortedBy {
+    pub(crate) fn new<F>(feeder: Rc<dyn Feeder>, compare: F) -> Self
+    where
+        F: Fn(&Suggestion, &Suggestion) -> Ordering + 'static,
+    {
+        SortedBy {
+            feeder,
+            compare: Rc::new(compare),
+        }
+    }
+}
+
+impl Feeder for SortedBy {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        values(self.suggest(text, position, items_count))
+    }
+
+    fn suggest(&self, text: &str, position: usize, items_count: usize) -> Vec<Suggestion> {
+        page(self.cursor(text).unwrap(), position, items_count)
+    }
+
+    /// All items of the feeder are read (and sorted) at once.
+    fn cursor(&self, text: &str) -> Option<Cursor> {
+        let mut items = Cursor::open(&self.feeder, text).collect::<Vec<_>>();
+        items.sort_by(|a, b| (self.compare)(a, b));
+        Some(Cursor::from_vec(items))
+    }
+
+    fn highlight(&self, text: &str, item: &str) -> Vec<usize> {
+        self.feeder.highlight(text, item)
+    }
+
+    fn error(&self, text: &str) -> Option<String> {
+        self.feeder.error(text)
+    }
+}
+
+/// Suggests at most a given count of items of a feeder (see [Feeder::limit]).
+///
+/// [Feeder::limit]: ./trait.Feeder.html#method.limit
+#[derive(Clone)]
+pub struct Limit {
+    feeder: Rc<dyn Feeder>,
+    limit: usize,
+}
+
+impl Limit {
+    pub(crate) fn new(feeder: Rc<dyn Feeder>, limit: usize) -> Self {
+        Limit { feeder, limit }
+    }
+}
+
+impl Feeder for Limit {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        values(self.suggest(text, position, items_count))
+    }
+
+    fn suggest(&self, text: &str, position: usize, items_count: usize) -> Vec<Suggestion> {
+        page(self.cursor(text).unwrap(), position, items_count)
+    }
+
+    fn cursor(&self, text: &str) -> Option<Cursor> {
+        let cursor = Cursor::open(&self.feeder, text);
+        let total = match cursor.total() {
+            Total::Exact(count) => Total::Exact(count.min(self.limit)),
+            Total::Unknown => Total::Unknown,
+        };
+        Some(Cursor::new(cursor.take(self.limit), total))
+    }
+
+    fn highlight(&self, text: &str, item: &str) -> Vec<usize> {
+        self.feeder.highlight(text, item)
+    }
+
+    fn error(&self, text: &str) -> Option<String> {
+        self.feeder.error(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use feeders::Items;
+    use std::cell::Cell;
+
+    /// Suggests numbers below 45, counting queries.
+    #[derive(Default)]
+    struct Counted {
+        queries: Rc<Cell<usize>>,
+    }
+
+    impl Feeder for Counted {
+        fn query(&self, _: &str, position: usize, items_count: usize) -> Vec<String> {
+            self.queries.set(self.queries.get() + 1);
+            (position..45.min(position + items_count))
+                .map(|x| x.to_string())
+                .collect()
+        }
+    }
+
+    #[test]
+    fn chain_pages_across_feeders() {
+        let feeder = chain(vec!["a", "b", "c"], vec!["d", "e"]);
+        assert_eq!(feeder.query("", 0, 2), vec!["a", "b"]);
+        assert_eq!(feeder.query("", 2, 2), vec!["c", "d"]);
+        assert_eq!(feeder.query("", 4, 2), vec!["e"]);
+        assert_eq!(feeder.query("", 5, 2), Vec::<String>::new());
+    }
+
+    #[test]
+    fn chain_queries_second_feeder_after_first_one() {
+        let queries = Rc::new(Cell::new(0));
+        let feeder = chain(
+            vec!["a", "b", "c"],
+            Counted {
+                queries: Rc::clone(&queries),
+            },
+        );
+        assert_eq!(feeder.query("", 0, 3), vec!["a", "b", "c"]);
+        assert_eq!(queries.get(), 0);
+        assert_eq!(feeder.query("", 2, 2), vec!["c", "0"]);
+        assert_eq!(queries.get(), 1);
+    }
+
+    #[test]
+    fn chain_of_paged_feeder_is_read_lazily() {
+        let queries = Rc::new(Cell::new(0));
+        let feeder = chain(
+            Counted {
+                queries: Rc::clone(&queries),
+            },
+            vec!["last"],
+        );
+        let mut cursor = feeder.cursor("").unwrap();
+        assert_eq!(cursor.total(), Total::Unknown);
+        assert_eq!(cursor.next(), Some("0".into()));
+        assert_eq!(queries.get(), 1);
+        assert_eq!(cursor.last(), Some("last".into()));
+        assert_eq!(queries.get(), 3);
+    }
+
+    #[test]
+    fn chain_highlights_by_either_feeder() {
+        let feeder = chain(vec!["abc"], Items::new(vec!["xyz"]));
+        assert_eq!(feeder.highlight("b", "abc"), vec![1]);
+        assert_eq!(feeder.highlight("y", "xyz"), vec![1]);
+    }
+
+    #[test]
+    fn filter_and_limit_keep_paging() {
+        let even = Counted::default().filter(|s| s.value.parse::<u32>().unwrap() % 2 == 0);
+        assert_eq!(even.query("", 2, 3), vec!["4", "6", "8"]);
+        assert_eq!(even.query("", 21, 3), vec!["42", "44"]);
+
+        let limited = even.limit(4);
+        assert_eq!(limited.query("", 2, 3), vec!["4", "6"]);
+        assert_eq!(limited.cursor("").unwrap().total(), Total::Unknown);
+        let exact = vec!["a", "b", "c"].limit(2);
+        assert_eq!(exact.cursor("").unwrap().total(), Total::Exact(2));
+    }
+
+    #[test]
+    fn map_changes_suggestions() {
+        let feeder = vec!["gzip", "xz"].map(|s| s.description("compression"));
+        let found = feeder.suggest("", 1, 1);
+        assert_eq!(
+            found,
+            vec![Suggestion::new("xz").description("compression")]
+        );
+        let upper = vec!["gzip"].map(|s| s.value.to_uppercase());
+        assert_eq!(upper.query("", 0, 10), vec!["GZIP"]);
+    }
+
+    #[test]
+    fn dedupe_keeps_first_of_values() {
+        let feeder = chain(
+            Items::from_suggestions(vec![Suggestion::new("b").description("recent")]),
+            vec!["a", "b", "c", "a"],
+        )
+        .dedupe();
+        let found = feeder.suggest("", 0, 10);
+        let found = found
+            .iter()
+            .map(|s| (s.value.as_str(), s.description.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(found, vec![("b", "recent"), ("a", ""), ("c", "")]);
+        assert_eq!(feeder.query("", 1, 1), vec!["a"]);
+    }
+
+    #[test]
+    fn sorted_by_reads_all_items() {
+        let feeder = vec!["b", "c", "a"].sorted_by(|a, b| b.value.cmp(&a.value));
+        assert_eq!(feeder.query("", 0, 10), vec!["c", "b", "a"]);
+        assert_eq!(feeder.query("", 1, 1), vec!["b"]);
+        assert_eq!(feeder.cursor("").unwrap().total(), Total::Exact(3));
+    }
+
+    #[test]
+    fn static_prefix_is_matched_and_first() {
+        let feeder = vec!["config.toml", "Cargo.toml"].with_static_prefix(vec!["recent.toml"]);
+        assert_eq!(
+            feeder.query("toml", 0, 10),
+            vec!["recent.toml", "Cargo.toml", "config.toml"]
+        );
+        assert_eq!(feeder.query("cfg", 0, 10), Vec::<String>::new());
+        assert_eq!(feeder.query("rec", 0, 10), vec!["recent.toml"]);
+    }
+
+    #[test]
+    fn errors_are_passed() {
+        struct Failing;
+        impl Feeder for Failing {
+            fn query(&self, _: &str, _: usize, _: usize) -> Vec<String> {
+                Vec::new()
+            }
+            fn error(&self, _: &str) -> Option<String> {
+                Some("failed".to_string())
+            }
+        }
+        assert_eq!(
+            chain(vec!["a"], Failing).error(""),
+            Some("failed".to_string())
+        );
+        assert_eq!(
+            Failing.limit(2).dedupe().error(""),
+            Some("failed".to_string())
+        );
+    }
+}
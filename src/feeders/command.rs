@@ -0,0 +1,429 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::Read;
+use std::process::{self, Stdio};
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[cfg(unix)]
+use libc;
+use serde_json::map::Map;
+use serde_json::value::Value;
+
+use super::matchers::{rank, Matcher, SmartCase, Substring};
+use super::{ContextFeeder, Cursor, Feeder};
+use messages::Message;
+
+/// Placeholder in args replaced by typed text.
+const PLACEHOLDER: &str = "{}";
+
+#[derive(Clone)]
+struct Cached {
+    at: Instant,
+    output: Result<Vec<String>, String>,
+}
+
+/// Suggests lines printed by a program.
+///
+/// Output is cached for [ttl]. Program is run while suggestions are queried
+/// (so form waits for it), if it runs longer than [timeout] it's killed with
+/// programs it started. If the program fails, views show its error in place
+/// of suggestions.
+///
+/// Lines are matched by [matcher] unless any of args contains placeholder `{}`,
+/// which is replaced by typed text (so the program does the filtering).
+/// In forms placeholders like `{repo}` are replaced by values of other fields
+/// (see [ContextFeeder]).
+///
+/// Program is run directly (not by shell), so each arg is passed as is.
+/// Don't put placeholders in scripts of `sh -c`, shell would run typed text
+/// as code.
+///
+/// ```
+/// # extern crate fui;
+/// # use fui::feeders::{Command, Feeder};
+/// # fn main() {
+/// let branches = Command::new("git").args(&["branch", "--format=%(refname:short)"]);
+/// let commits = Command::new("git").args(&["log", "--format=%s", "-i", "--grep", "{}"]);
+///
+/// let letters = Command::new("printf").arg("alpha\\nbeta\\n");
+/// assert_eq!(letters.query("b", 0, 10), vec!["beta"]);
+/// # }
+/// ```
+///
+/// [ttl]: #method.ttl
+/// [timeout]: #method.timeout
+/// [matcher]: #method.matcher
+/// [ContextFeeder]: ./trait.ContextFeeder.html
+#[derive(Clone)]
+pub struct Command {
+    program: String,
+    args: Vec<String>,
+    ttl: Duration,
+    timeout: Duration,
+    matcher: Rc<dyn Matcher>,
+    cache: RefCell<HashMap<String, Cached>>,
+}
+
+impl Command {
+    /// Creates a new `Command` running `program`.
+    pub fn new<IS: Into<String>>(program: IS) -> Self {
+        Command {
+            program: program.into(),
+            args: Vec::new(),
+            ttl: Duration::from_secs(5),
+            timeout: Duration::from_secs(2),
+            matcher: Rc::new(SmartCase(Substring)),
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Adds `arg` passed to program.
+    pub fn arg<IS: Into<String>>(mut self, arg: IS) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Adds `args` passed to program.
+    pub fn args<IS: Into<String> + Clone>(mut self, args: &[IS]) -> Self {
+        self.args.extend(args.iter().cloned().map(Into::into));
+        self
+    }
+
+    /// Sets how long output is reused (default: 5s).
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets how long program may run (default: 2s).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets [Matcher] filtering and ranking lines.
+    ///
+    /// [Matcher]: ./matchers/trait.Matcher.html
+    pub fn matcher<M: Matcher>(mut self, matcher: M) -> Self {
+        self.matcher = Rc::new(matcher);
+        self
+    }
+
+    fn uses_text(&self) -> bool {
+        self.args.iter().any(|a| a.contains(PLACEHOLDER))
+    }
+
+    /// Returns args with placeholders replaced by `values` and `text`.
+    fn args_with(&self, text: &str, values: &Map<String, Value>) -> Vec<String> {
+        self.args
+            .iter()
+            .map(|a| {
+                let mut arg = a.clone();
+                for (label, value) in values.iter() {
+                    let value = match *value {
+                        Value::String(ref s) => s.clone(),
+                        ref other => other.to_string(),
+                    };
+                    arg = arg.replace(&format!("{{{}}}", label), &value);
+                }
+                arg.replace(PLACEHOLDER, text)
+            })
+            .collect()
+    }
+
+    /// Returns cached output or runs program.
+    fn output(&self, text: &str, values: &Map<String, Value>) -> Result<Vec<String>, String> {
+        let args = self.args_with(text, values);
+        let key = args.join("\0");
+        if let Some(cached) = self.cache.borrow().get(&key) {
+            if cached.at.elapsed() < self.ttl {
+                return cached.output.clone();
+            }
+        }
+        let output = self.run(&args);
+        let mut cache = self.cache.borrow_mut();
+        let ttl = self.ttl;
+        cache.retain(|_, c| c.at.elapsed() < ttl);
+        cache.insert(
+            key,
+            Cached {
+                at: Instant::now(),
+                output: output.clone(),
+            },
+        );
+        output
+    }
+
+    fn run(&self, args: &[String]) -> Result<Vec<String>, String> {
+        let command = Some(&self.program)
+            .into_iter()
+            .chain(args.iter())
+            .cloned()
+            .collect::<Vec<String>>()
+            .join(" ");
+        let failed = |error: String| {
+            Message::new("feeders.command_failed")
+                .param("command", &command)
+                .param("error", error)
+                .to_string()
+        };
+
+        let mut cmd = process::Command::new(&self.program);
+        cmd.args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        own_process_group(&mut cmd);
+        let mut child = cmd.spawn().map_err(|e| failed(e.to_string()))?;
+        let stdout = read_in_background(child.stdout.take());
+        let stderr = read_in_background(child.stderr.take());
+
+        let deadline = Instant::now() + self.timeout;
+        let timed_out = |mut child: process::Child| {
+            // killing program's children closes pipes too, so readers end
+            kill(&mut child);
+            let _ = child.wait();
+            Message::new("feeders.command_timeout")
+                .param("command", &command)
+                .param("timeout", format!("{:?}", self.timeout))
+                .to_string()
+        };
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) if Instant::now() < deadline => {
+                    thread::sleep(Duration::from_millis(10));
+                }
+                Ok(None) => return Err(timed_out(child)),
+                Err(e) => {
+                    kill(&mut child);
+                    let _ = child.wait();
+                    return Err(failed(e.to_string()));
+                }
+            }
+        };
+
+        // program's children may keep pipes open after it exits
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let stdout = match stdout.recv_timeout(remaining) {
+            Ok(stdout) => stdout,
+            Err(_) => return Err(timed_out(child)),
+        };
+        if !status.success() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let stderr = stderr.recv_timeout(remaining).unwrap_or_default();
+            let error = match stderr.lines().find(|l| !l.trim().is_empty()) {
+                Some(line) => format!("{}: {}", status, line.trim()),
+                None => status.to_string(),
+            };
+            return Err(failed(error));
+        }
+        Ok(stdout
+            .lines()
+            .map(|l| l.trim_end().to_string())
+            .filter(|l| !l.is_empty())
+            .collect())
+    }
+}
+
+/// Reads `source` to the end in a new thread, which sends the read text.
+///
+/// Thread ends when `source` is closed, even if nobody waits for the text.
+fn read_in_background<R: Read + Send + 'static>(source: Option<R>) -> mpsc::Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut data = Vec::new();
+        if let Some(mut source) = source {
+            let _ = source.read_to_end(&mut data);
+        }
+        let _ = sender.send(String::from_utf8_lossy(&data).into_owned());
+    });
+    receiver
+}
+
+/// Makes program leader of a new process group, so its children can be
+/// killed with it.
+#[cfg(unix)]
+fn own_process_group(command: &mut process::Command) {
+    use std::os::unix::process::CommandExt;
+    command.process_group(0);
+}
+
+#[cfg(not(unix))]
+fn own_process_group(_command: &mut process::Command) {}
+
+/// Kills `child` with programs it started (on Unix).
+#[cfg(unix)]
+fn kill(child: &mut process::Child) {
+    // group of child has its pid (see `own_process_group`)
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+    let _ = child.kill();
+}
+
+#[cfg(not(unix))]
+fn kill(child: &mut process::Child) {
+    let _ = child.kill();
+}
+
+impl Feeder for Command {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        ContextFeeder::query(self, text, &Map::new(), position, items_count)
+    }
+
+    fn cursor(&self, text: &str) -> Option<Cursor> {
+        Some(Cursor::from_vec(Feeder::query(self, text, 0, usize::MAX)))
+    }
+
+    fn highlight(&self, text: &str, item: &str) -> Vec<usize> {
+        ContextFeeder::highlight(self, text, &Map::new(), item)
+    }
+
+    fn error(&self, text: &str) -> Option<String> {
+        ContextFeeder::error(self, text, &Map::new())
+    }
+}
+
+/// Args may contain placeholders like `{repo}` replaced by value of field `repo`.
+impl ContextFeeder for Command {
+    fn query(
+        &self,
+        text: &str,
+        values: &Map<String, Value>,
+        position: usize,
+        items_count: usize,
+    ) -> Vec<String> {
+        let lines = self.output(text, values).unwrap_or_default();
+        if self.uses_text() {
+            lines.into_iter().skip(position).take(items_count).collect()
+        } else {
+            rank(
+                &*self.matcher,
+                text,
+                lines.into_iter(),
+                position,
+                items_count,
+            )
+        }
+    }
+
+    fn highlight(&self, text: &str, _values: &Map<String, Value>, item: &str) -> Vec<usize> {
+        if self.uses_text() {
+            return Vec::new();
+        }
+        self.matcher
+            .find(text, item)
+            .map(|m| m.positions)
+            .unwrap_or_default()
+    }
+
+    fn error(&self, text: &str, values: &Map<String, Value>) -> Option<String> {
+        self.output(text, values).err()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Command;
+    use feeders::Feeder;
+    use serde_json::map::Map;
+    use serde_json::value::Value;
+    use std::fs;
+    use std::process;
+    use std::thread;
+    use std::time::{Duration, Instant};
+    use test_utils::TempDir;
+
+    #[test]
+    fn lines_are_matched_and_ranked() {
+        let feeder = Command::new("printf").arg("alpha\\nbeta\\n\\ngamma\\n");
+        assert_eq!(feeder.query("", 0, 10), vec!["alpha", "beta", "gamma"]);
+        assert_eq!(feeder.query("a", 0, 10), vec!["alpha", "gamma", "beta"]);
+        assert_eq!(feeder.error("a"), None);
+    }
+
+    #[test]
+    fn typed_text_is_substituted() {
+        let feeder = Command::new("echo").arg("x-{}");
+        assert_eq!(feeder.query("abc", 0, 10), vec!["x-abc"]);
+        assert_eq!(feeder.query("zzz", 0, 10), vec!["x-zzz"]);
+    }
+
+    #[test]
+    fn field_values_are_substituted() {
+        let feeder = Command::new("echo").arg("{repo}/{}");
+        let mut values = Map::new();
+        values.insert("repo".to_string(), Value::from("fui"));
+        assert_eq!(
+            ::feeders::ContextFeeder::query(&feeder, "src", &values, 0, 10),
+            vec!["fui/src"]
+        );
+    }
+
+    #[test]
+    fn output_is_cached_for_ttl() {
+        let cached = Command::new("sh").args(&["-c", "echo $$"]);
+        assert_eq!(cached.query("", 0, 1), cached.query("", 0, 1));
+
+        let uncached = cached.clone().ttl(Duration::from_secs(0));
+        assert_ne!(uncached.query("", 0, 1), uncached.query("", 0, 1));
+    }
+
+    #[test]
+    fn failure_is_error() {
+        let feeder = Command::new("sh").args(&["-c", "echo oops >&2; exit 3"]);
+        assert_eq!(feeder.query("", 0, 10), Vec::<String>::new());
+        let error = feeder.error("").unwrap();
+        assert!(error.contains("sh -c"), "{}", error);
+        assert!(error.contains("oops"), "{}", error);
+
+        let missing = Command::new("fui-missing-program");
+        assert!(missing.error("").is_some());
+    }
+
+    #[test]
+    fn program_is_killed_with_its_children() {
+        let dir = TempDir::new("command");
+        let pid_file = dir.join("pid");
+        // child keeps stdout open after program exits
+        let script = format!("sleep 30 & echo $! > {:?}; echo done", pid_file);
+        let feeder = Command::new("sh")
+            .args(&["-c", &script])
+            .timeout(Duration::from_millis(300));
+        let started = Instant::now();
+        assert!(feeder.error("").unwrap().contains("timed out"));
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        let pid = fs::read_to_string(&pid_file).unwrap();
+        // killed child is left as zombie until init reaps it
+        let running = || {
+            let output = process::Command::new("ps")
+                .args(["-o", "stat=", "-p", pid.trim()])
+                .output()
+                .unwrap();
+            let state = String::from_utf8_lossy(&output.stdout);
+            !state.trim().is_empty() && !state.starts_with('Z')
+        };
+        for _ in 0..100 {
+            if !running() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(!running());
+    }
+
+    #[test]
+    fn slow_program_is_killed() {
+        let feeder = Command::new("sleep")
+            .arg("5")
+            .timeout(Duration::from_millis(100));
+        let started = Instant::now();
+        assert!(feeder.error("").unwrap().contains("timed out"));
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
@@ -0,0 +1,147 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde_json::map::Map;
+use serde_json::value::Value;
+
+use super::matchers::{rank, Matcher, SmartCase, Substring};
+use super::{Feeder, Suggestion};
+
+/// Makes data querable with values of other fields of the form.
+///
+/// Closures taking `values` and returning items are context feeders too,
+/// their items are matched like `Vec`'s items.
+///
+/// ```
+/// # extern crate fui;
+/// # extern crate serde_json;
+/// # use fui::feeders::{ContextFeeder, Contextual, Feeder};
+/// # use fui::Map;
+/// # use serde_json::value::Value;
+/// # fn main() {
+/// let tables = |values: &Map<String, Value>| match values.get("db").and_then(|v| v.as_str()) {
+///     Some("shop") => vec!["orders".to_string(), "products".to_string()],
+///     _ => Vec::new(),
+/// };
+/// let feeder = Contextual::new(tables);
+/// assert_eq!(feeder.query("", 0, 10), Vec::<String>::new());
+///
+/// let mut values = Map::new();
+/// values.insert("db".to_string(), Value::from("shop"));
+/// feeder.set_values(values);
+/// assert_eq!(feeder.query("prod", 0, 10), vec!["products"]);
+/// # }
+/// ```
+pub trait ContextFeeder: 'static {
+    /// Returns data filtered by `text` and `values` of other fields,
+    /// `position` limited to `items_count`.
+    fn query(
+        &self,
+        text: &str,
+        values: &Map<String, Value>,
+        position: usize,
+        items_count: usize,
+    ) -> Vec<String>;
+
+    /// Returns [Suggestion]s filtered by `text` and `values` of other fields,
+    /// `position` limited to `items_count`.
+    ///
+    /// By default values returned by `query` are shown as they are.
+    ///
+    /// [Suggestion]: ./struct.Suggestion.html
+    fn suggest(
+        &self,
+        text: &str,
+        values: &Map<String, Value>,
+        position: usize,
+        items_count: usize,
+    ) -> Vec<Suggestion> {
+        self.query(text, values, position, items_count)
+            .into_iter()
+            .map(Suggestion::from)
+            .collect()
+    }
+
+    /// Returns positions (in chars) of `item`'s characters matching `text`.
+    fn highlight(&self, _text: &str, _values: &Map<String, Value>, _item: &str) -> Vec<usize> {
+        Vec::new()
+    }
+
+    /// Returns error which occurred while querying `text` with `values`.
+    fn error(&self, _text: &str, _values: &Map<String, Value>) -> Option<String> {
+        None
+    }
+}
+
+impl<F> ContextFeeder for F
+where
+    F: Fn(&Map<String, Value>) -> Vec<String> + 'static,
+{
+    fn query(
+        &self,
+        text: &str,
+        values: &Map<String, Value>,
+        position: usize,
+        items_count: usize,
+    ) -> Vec<String> {
+        let items = self(values).into_iter();
+        rank(&SmartCase(Substring), text, items, position, items_count)
+    }
+
+    fn highlight(&self, text: &str, _values: &Map<String, Value>, item: &str) -> Vec<usize> {
+        SmartCase(Substring)
+            .find(text, item)
+            .map(|m| m.positions)
+            .unwrap_or_default()
+    }
+}
+
+/// [Feeder] which queries [ContextFeeder] with the latest values of other fields.
+///
+/// Values are set by form (see [FormView::refresh_context]).
+///
+/// [Feeder]: ./trait.Feeder.html
+/// [ContextFeeder]: ./trait.ContextFeeder.html
+/// [FormView::refresh_context]: ../form/struct.FormView.html#method.refresh_context
+#[derive(Clone)]
+pub struct Contextual {
+    feeder: Rc<dyn ContextFeeder>,
+    values: Rc<RefCell<Map<String, Value>>>,
+}
+
+impl Contextual {
+    /// Creates a new `Contextual` with no values.
+    pub fn new<F: ContextFeeder>(feeder: F) -> Self {
+        Contextual {
+            feeder: Rc::new(feeder),
+            values: Rc::new(RefCell::new(Map::new())),
+        }
+    }
+
+    /// Sets `values` of other fields.
+    ///
+    /// It's shared with clones of this `Contextual`.
+    pub fn set_values(&self, values: Map<String, Value>) {
+        *self.values.borrow_mut() = values;
+    }
+}
+
+impl Feeder for Contextual {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        self.feeder
+            .query(text, &self.values.borrow(), position, items_count)
+    }
+
+    fn suggest(&self, text: &str, position: usize, items_count: usize) -> Vec<Suggestion> {
+        self.feeder
+            .suggest(text, &self.values.borrow(), position, items_count)
+    }
+
+    fn highlight(&self, text: &str, item: &str) -> Vec<usize> {
+        self.feeder.highlight(text, &self.values.borrow(), item)
+    }
+
+    fn error(&self, text: &str) -> Option<String> {
+        self.feeder.error(text, &self.values.borrow())
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt;
+use std::rc::Rc;
+use std::vec;
+
+use super::{Feeder, Suggestion};
+
+/// How many items [Cursor::open] asks `suggest` for at once.
+///
+/// [Cursor::open]: ./struct.Cursor.html#method.open
+const PAGE_SIZE: usize = 20;
+
+/// Total count of items matching a query.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Total {
+    /// Count is known.
+    Exact(usize),
+    /// Count is known after reading all items.
+    Unknown,
+}
+
+/// Suggestions matching a query, read lazily (see [Feeder::cursor]).
+///
+/// ```
+/// # extern crate fui;
+/// # use fui::feeders::{Cursor, Suggestion, Total};
+/// # fn main() {
+/// let mut cursor = Cursor::from_vec(vec!["a", "b"]);
+/// assert_eq!(cursor.total(), Total::Exact(2));
+/// assert_eq!(cursor.next(), Some(Suggestion::new("a")));
+///
+/// let cursor = Cursor::new((0..).map(|x| x.to_string()), Total::Unknown);
+/// let values = cursor.take(3).map(|s| s.value).collect::<Vec<String>>();
+/// assert_eq!(values, vec!["0", "1", "2"]);
+/// # }
+/// ```
+///
+/// [Feeder::cursor]: ./trait.Feeder.html#method.cursor
+pub struct Cursor {
+    items: Box<dyn Iterator<Item = Suggestion>>,
+    total: Total,
+}
+
+impl Cursor {
+    /// Creates a new `Cursor` reading `items` (values or [Suggestion]s).
+    ///
+    /// [Suggestion]: ./struct.Suggestion.html
+    pub fn new<T, I>(items: I, total: Total) -> Self
+    where
+        T: Into<Suggestion> + 'static,
+        I: Iterator<Item = T> + 'static,
+    {
+        Cursor {
+            items: Box::new(items.map(Into::into)),
+            total,
+        }
+    }
+
+    /// Creates a new `Cursor` reading already found `items`.
+    pub fn from_vec<T: Into<Suggestion> + 'static>(items: Vec<T>) -> Self {
+        let total = Total::Exact(items.len());
+        Cursor::new(items.into_iter(), total)
+    }
+
+    /// Creates a new `Cursor` without items.
+    pub fn empty() -> Self {
+        Cursor::from_vec(Vec::<Suggestion>::new())
+    }
+
+    /// Opens cursor of `feeder` for `text`.
+    ///
+    /// Feeders which don't provide cursors are read page by page with
+    /// [Feeder::suggest].
+    ///
+    /// [Feeder::suggest]: ./trait.Feeder.html#method.suggest
+    pub fn open(feeder: &Rc<dyn Feeder>, text: &str) -> Self {
+        match feeder.cursor(text) {
+            Some(cursor) => cursor,
+            None => Cursor::new(
+                Pages {
+                    feeder: Rc::clone(feeder),
+                    text: text.to_string(),
+                    position: 0,
+                    page: Vec::new().into_iter(),
+                    finished: false,
+                },
+                Total::Unknown,
+            ),
+        }
+    }
+
+    /// Returns total count of items (including already read ones).
+    pub fn total(&self) -> Total {
+        self.total
+    }
+}
+
+impl Iterator for Cursor {
+    type Item = Suggestion;
+
+    fn next(&mut self) -> Option<Suggestion> {
+        self.items.next()
+    }
+}
+
+impl fmt::Debug for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Cursor {{ total: {:?} }}", self.total)
+    }
+}
+
+/// Reads suggestions of feeder with `suggest` page by page.
+struct Pages {
+    feeder: Rc<dyn Feeder>,
+    text: String,
+    position: usize,
+    page: vec::IntoIter<Suggestion>,
+    finished: bool,
+}
+
+impl Iterator for Pages {
+    type Item = Suggestion;
+
+    fn next(&mut self) -> Option<Suggestion> {
+        if let Some(item) = self.page.next() {
+            return Some(item);
+        }
+        if self.finished {
+            return None;
+        }
+        let page = self.feeder.suggest(&self.text, self.position, PAGE_SIZE);
+        self.position += page.len();
+        self.finished = page.len() < PAGE_SIZE;
+        self.page = page.into_iter();
+        self.page.next()
+    }
+}
@@ -0,0 +1,446 @@
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::SystemTime;
+
+use serde_json;
+use serde_json::value::Value;
+
+use super::matchers::{rank_by, Matcher, SmartCase, Substring};
+use super::{Cursor, Feeder, Suggestion};
+use messages::Message;
+
+#[derive(Clone, Debug)]
+enum Format {
+    Lines,
+    Csv,
+    Json,
+}
+
+#[derive(Clone)]
+struct Loaded {
+    // modification time & size of the file when it was read
+    stamp: (SystemTime, u64),
+    items: Result<Vec<Suggestion>, String>,
+}
+
+/// Suggests items loaded from a file (lines, CSV or JSON).
+///
+/// The file is loaded again when its modification time changes. If it
+/// can't be loaded, views show the error in place of suggestions.
+///
+/// Items are matched by [matcher] against their display text, which (like
+/// description) is taken from a CSV column or a key of JSON objects.
+///
+/// ```
+/// # extern crate fui;
+/// # use fui::feeders::{Feeder, FileItems};
+/// # use std::fs;
+/// # fn main() {
+/// # let dir = std::env::temp_dir();
+/// # let path = dir.join("fui-file-items-doc.csv");
+/// fs::write(&path, "name,help\ngzip,\"fast, moderate ratio\"\nxz,slow\n").unwrap();
+/// let csv = FileItems::csv(&path).value("name").description("help");
+/// assert_eq!(csv.suggest("gz", 0, 10)[0].description, "fast, moderate ratio");
+///
+/// let lines = FileItems::lines("/etc/shells");
+/// let json = FileItems::json("options.json")
+///     .pointer("/compression")
+///     .value("name")
+///     .display("label");
+/// # }
+/// ```
+///
+/// [matcher]: #method.matcher
+#[derive(Clone)]
+pub struct FileItems {
+    path: PathBuf,
+    format: Format,
+    pointer: String,
+    value: Option<String>,
+    display: Option<String>,
+    description: Option<String>,
+    matcher: Rc<dyn Matcher>,
+    loaded: RefCell<Option<Loaded>>,
+}
+
+impl FileItems {
+    fn new(path: PathBuf, format: Format) -> Self {
+        FileItems {
+            path,
+            format,
+            pointer: String::new(),
+            value: None,
+            display: None,
+            description: None,
+            matcher: Rc::new(SmartCase(Substring)),
+            loaded: RefCell::new(None),
+        }
+    }
+
+    /// Creates a new `FileItems` suggesting non-empty lines of file at `path`.
+    pub fn lines<P: Into<PathBuf>>(path: P) -> Self {
+        FileItems::new(path.into(), Format::Lines)
+    }
+
+    /// Creates a new `FileItems` suggesting rows of CSV file (with header) at `path`.
+    ///
+    /// Values are taken from the first column, unless [value] sets other one.
+    ///
+    /// [value]: #method.value
+    pub fn csv<P: Into<PathBuf>>(path: P) -> Self {
+        FileItems::new(path.into(), Format::Csv)
+    }
+
+    /// Creates a new `FileItems` suggesting items of JSON array in file at `path`.
+    ///
+    /// Items are strings (or numbers, booleans) or objects with key `value`
+    /// (see [value]).
+    ///
+    /// [value]: #method.value
+    pub fn json<P: Into<PathBuf>>(path: P) -> Self {
+        FileItems::new(path.into(), Format::Json)
+    }
+
+    /// Sets JSON pointer (like `/options/compression`) of array with items.
+    pub fn pointer<IS: Into<String>>(mut self, pointer: IS) -> Self {
+        self.pointer = pointer.into();
+        self
+    }
+
+    /// Sets CSV column or key of JSON objects with values.
+    pub fn value<IS: Into<String>>(mut self, value: IS) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Sets CSV column or key of JSON objects with texts shown in place of values.
+    pub fn display<IS: Into<String>>(mut self, display: IS) -> Self {
+        self.display = Some(display.into());
+        self
+    }
+
+    /// Sets CSV column or key of JSON objects with descriptions.
+    pub fn description<IS: Into<String>>(mut self, description: IS) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets [Matcher] filtering and ranking items.
+    ///
+    /// [Matcher]: ./matchers/trait.Matcher.html
+    pub fn matcher<M: Matcher>(mut self, matcher: M) -> Self {
+        self.matcher = Rc::new(matcher);
+        self
+    }
+
+    /// Returns items of the file, it's read only if it changed.
+    fn load(&self) -> Result<Vec<Suggestion>, String> {
+        let failed = |error: io::Error| {
+            Message::new("feeders.file_failed")
+                .param("path", self.path.display().to_string())
+                .param("error", error.to_string())
+                .to_string()
+        };
+        let stamp = fs::metadata(&self.path)
+            .and_then(|m| Ok((m.modified()?, m.len())))
+            .map_err(failed)?;
+        if let Some(ref loaded) = *self.loaded.borrow() {
+            if loaded.stamp == stamp {
+                return loaded.items.clone();
+            }
+        }
+        let items = fs::read_to_string(&self.path)
+            .map_err(failed)
+            .and_then(|content| {
+                self.parse(&content).map_err(|error| {
+                    Message::new("feeders.file_invalid")
+                        .param("path", self.path.display().to_string())
+                        .param("error", error)
+                        .to_string()
+                })
+            });
+        *self.loaded.borrow_mut() = Some(Loaded {
+            stamp,
+            items: items.clone(),
+        });
+        items
+    }
+
+    fn parse(&self, content: &str) -> Result<Vec<Suggestion>, String> {
+        match self.format {
+            Format::Lines => Ok(content
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(Suggestion::new)
+                .collect()),
+            Format::Csv => self.parse_csv(content),
+            Format::Json => self.parse_json(content),
+        }
+    }
+
+    fn parse_csv(&self, content: &str) -> Result<Vec<Suggestion>, String> {
+        let mut rows = parse_csv(content)?.into_iter();
+        let header = rows.next().unwrap_or_default();
+        let column = |name: &Option<String>| -> Result<Option<usize>, String> {
+            match *name {
+                Some(ref name) => match header.iter().position(|h| h == name) {
+                    Some(idx) => Ok(Some(idx)),
+                    None => Err(format!("missing column {:?}", name)),
+                },
+                None => Ok(None),
+            }
+        };
+        let value = column(&self.value)?.unwrap_or(0);
+        let display = column(&self.display)?;
+        let description = column(&self.description)?;
+
+        let cell = |row: &Vec<String>, idx: usize| row.get(idx).cloned().unwrap_or_default();
+        Ok(rows
+            .filter(|row| !cell(row, value).is_empty())
+            .map(|row| {
+                let mut suggestion = Suggestion::new(cell(&row, value));
+                if let Some(idx) = display {
+                    suggestion = suggestion.display(cell(&row, idx));
+                }
+                if let Some(idx) = description {
+                    suggestion = suggestion.description(cell(&row, idx));
+                }
+                suggestion
+            })
+            .collect())
+    }
+
+    fn parse_json(&self, content: &str) -> Result<Vec<Suggestion>, String> {
+        let json: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        let items = json
+            .pointer(&self.pointer)
+            .and_then(Value::as_array)
+            .ok_or_else(|| format!("no array at {:?}", self.pointer))?;
+        let value_key = self.value.clone().unwrap_or_else(|| "value".to_string());
+        Ok(items
+            .iter()
+            .filter_map(|item| match *item {
+                Value::Object(ref object) => {
+                    let text = |key: &Option<String>| {
+                        key.as_ref().and_then(|k| object.get(k)).map(as_text)
+                    };
+                    let mut suggestion = Suggestion::new(as_text(object.get(&value_key)?));
+                    if let Some(display) = text(&self.display) {
+                        suggestion = suggestion.display(display);
+                    }
+                    if let Some(description) = text(&self.description) {
+                        suggestion = suggestion.description(description);
+                    }
+                    Some(suggestion)
+                }
+                Value::Null | Value::Array(_) => None,
+                ref other => Some(Suggestion::new(as_text(other))),
+            })
+            .collect())
+    }
+}
+
+/// Returns JSON `value` as text (strings without quotes).
+fn as_text(value: &Value) -> String {
+    match *value {
+        Value::String(ref s) => s.clone(),
+        ref other => other.to_string(),
+    }
+}
+
+/// Splits CSV `content` to rows of cells (quoted cells may contain
+/// separators, newlines and doubled quotes).
+fn parse_csv(content: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    cell.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if cell.is_empty() => quoted = true,
+            ',' if !quoted => row.push(cell.split_off(0)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                row.push(cell.split_off(0));
+                if row.iter().any(|c| !c.is_empty()) {
+                    rows.push(row.split_off(0));
+                }
+                row.clear();
+            }
+            c => cell.push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quoted cell".to_string());
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+impl Feeder for FileItems {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        self.suggest(text, position, items_count)
+            .into_iter()
+            .map(|s| s.value)
+            .collect()
+    }
+
+    fn suggest(&self, text: &str, position: usize, items_count: usize) -> Vec<Suggestion> {
+        let items = self.load().unwrap_or_default().into_iter();
+        rank_by(
+            &*self.matcher,
+            text,
+            items,
+            |s| &s.display,
+            position,
+            items_count,
+        )
+    }
+
+    fn cursor(&self, text: &str) -> Option<Cursor> {
+        Some(Cursor::from_vec(self.suggest(text, 0, usize::MAX)))
+    }
+
+    fn highlight(&self, text: &str, item: &str) -> Vec<usize> {
+        self.matcher
+            .find(text, item)
+            .map(|m| m.positions)
+            .unwrap_or_default()
+    }
+
+    fn error(&self, _text: &str) -> Option<String> {
+        self.load().err()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_utils::TempDir;
+
+    /// Writes `content` to file `name` in a new temporary dir.
+    fn file(name: &str, content: &str) -> (TempDir, PathBuf) {
+        let dir = TempDir::new("file-items").file(name, content);
+        let path = dir.join(name);
+        (dir, path)
+    }
+
+    fn described(found: Vec<Suggestion>) -> Vec<(String, String, String)> {
+        found
+            .into_iter()
+            .map(|s| (s.value, s.display, s.description))
+            .collect()
+    }
+
+    #[test]
+    fn lines_are_suggested() {
+        let (_dir, path) = file("lines.txt", "gzip\n\n  xz \nbzip2\n");
+        let feeder = FileItems::lines(&path);
+        assert_eq!(feeder.query("", 0, 10), vec!["gzip", "xz", "bzip2"]);
+        assert_eq!(feeder.query("z", 1, 10), vec!["xz", "bzip2"]);
+    }
+
+    #[test]
+    fn file_is_reloaded_when_changed() {
+        let (_dir, path) = file("reload.txt", "gzip\n");
+        let feeder = FileItems::lines(&path);
+        assert_eq!(feeder.query("", 0, 10), vec!["gzip"]);
+        fs::write(&path, "gzip\nxz\n").unwrap();
+        assert_eq!(feeder.query("", 0, 10), vec!["gzip", "xz"]);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(feeder.query("", 0, 10), Vec::<String>::new());
+        assert!(feeder.error("").unwrap().starts_with("Can't read"));
+    }
+
+    #[test]
+    fn csv_columns_are_mapped() {
+        let (_dir, path) = file(
+            "columns.csv",
+            "label,name,help\r\nGzip,gzip,\"fast, \"\"moderate\"\" ratio\"\r\n,,\nXZ,xz,\"slow,\nhigh ratio\"",
+        );
+        let feeder = FileItems::csv(&path)
+            .value("name")
+            .display("label")
+            .description("help");
+        assert_eq!(
+            described(feeder.suggest("", 0, 10)),
+            vec![
+                (
+                    "gzip".to_string(),
+                    "Gzip".to_string(),
+                    "fast, \"moderate\" ratio".to_string()
+                ),
+                (
+                    "xz".to_string(),
+                    "XZ".to_string(),
+                    "slow,\nhigh ratio".to_string()
+                ),
+            ]
+        );
+        assert_eq!(FileItems::csv(&path).query("", 0, 10), vec!["Gzip", "XZ"]);
+        let missing = FileItems::csv(&path).description("missing");
+        assert!(missing
+            .error("")
+            .unwrap()
+            .contains("missing column \"missing\""));
+    }
+
+    #[test]
+    fn json_items_are_found_by_pointer() {
+        let (_dir, path) = file(
+            "pointer.json",
+            r#"{"options": {"compression": [
+                {"name": "gzip", "label": "GNU zip", "help": "fast"},
+                {"label": "no name"},
+                "xz", 7, null
+            ]}}"#,
+        );
+        let feeder = FileItems::json(&path)
+            .pointer("/options/compression")
+            .value("name")
+            .display("label")
+            .description("help");
+        assert_eq!(
+            described(feeder.suggest("", 0, 10)),
+            vec![
+                (
+                    "gzip".to_string(),
+                    "GNU zip".to_string(),
+                    "fast".to_string()
+                ),
+                ("xz".to_string(), "xz".to_string(), "".to_string()),
+                ("7".to_string(), "7".to_string(), "".to_string()),
+            ]
+        );
+        assert_eq!(feeder.query("gnu", 0, 10), vec!["gzip"]);
+        let missing = FileItems::json(&path).pointer("/missing");
+        assert!(missing
+            .error("")
+            .unwrap()
+            .contains("no array at \"/missing\""));
+    }
+
+    #[test]
+    fn invalid_json_is_error() {
+        let (_dir, path) = file("invalid.json", "[\"gzip\",");
+        let feeder = FileItems::json(&path);
+        assert_eq!(feeder.query("", 0, 10), Vec::<String>::new());
+        assert!(feeder.error("").unwrap().contains("is invalid"));
+    }
+}
@@ -0,0 +1,397 @@
+//! Feeders suggesting data of a local git repository.
+//!
+//! They run local `git` binary (see [Command]) in the current dir, in a
+//! fixed [repo] or in a repo taken from other field of the form ([repo_field]).
+//!
+//! ```
+//! # extern crate fui;
+//! # use fui::feeders::git;
+//! # use fui::fields::Autocomplete;
+//! # use fui::form::FormView;
+//! # fn main() {
+//! let branches = git::branches().repo("/path/to/repo");
+//! let form = FormView::new()
+//!     .field(Autocomplete::new("path", git::files()))
+//!     .field(Autocomplete::with_context("commit", git::commits().repo_field("repo")));
+//! # }
+//! ```
+//!
+//! [Command]: ../struct.Command.html
+//! [repo]: ./struct.Git.html#method.repo
+//! [repo_field]: ./struct.Git.html#method.repo_field
+
+use std::path::Path;
+use std::rc::Rc;
+
+use serde_json::map::Map;
+use serde_json::value::Value;
+
+use super::matchers::{rank_by, Matcher, SmartCase, Substring};
+use super::{Command, ContextFeeder, Cursor, Feeder, Suggestion};
+
+/// How many recent commits are suggested.
+const COMMITS_COUNT: usize = 100;
+
+/// Suggests data of a git repository (created by functions of this module).
+///
+/// If `git` fails (like outside of a repository), views show its error
+/// in place of suggestions.
+#[derive(Clone)]
+pub struct Git {
+    args: Vec<String>,
+    kind: &'static str,
+    // symbolic refs like `origin/HEAD` aren't suggested
+    skip_head: bool,
+    // only hash (the first word of line) is inserted
+    hash_prefix: bool,
+    repo_field: Option<String>,
+    command: Command,
+    matcher: Rc<dyn Matcher>,
+}
+
+/// Creates [Git] suggesting local branches.
+///
+/// [Git]: ./struct.Git.html
+pub fn branches() -> Git {
+    Git::new(
+        "branch",
+        &[
+            "for-each-ref",
+            "--format=%(refname:lstrip=2)",
+            "refs/heads/",
+        ],
+    )
+}
+
+/// Creates [Git] suggesting remote branches (like `origin/master`).
+///
+/// [Git]: ./struct.Git.html
+pub fn remote_branches() -> Git {
+    Git {
+        skip_head: true,
+        ..Git::new(
+            "remote_branch",
+            &[
+                "for-each-ref",
+                "--format=%(refname:lstrip=2)",
+                "refs/remotes/",
+            ],
+        )
+    }
+}
+
+/// Creates [Git] suggesting tags.
+///
+/// [Git]: ./struct.Git.html
+pub fn tags() -> Git {
+    Git::new(
+        "tag",
+        &["for-each-ref", "--format=%(refname:lstrip=2)", "refs/tags/"],
+    )
+}
+
+/// Creates [Git] suggesting remotes.
+///
+/// [Git]: ./struct.Git.html
+pub fn remotes() -> Git {
+    Git::new("remote", &["remote"])
+}
+
+/// Creates [Git] suggesting recent commits of `HEAD` shown with their
+/// subjects, only short hashes are inserted.
+///
+/// [Git]: ./struct.Git.html
+pub fn commits() -> Git {
+    let count = format!("--max-count={}", COMMITS_COUNT);
+    Git {
+        hash_prefix: true,
+        ..Git::new("commit", &["log", &count, "--format=%h %s"])
+    }
+}
+
+/// Creates [Git] suggesting tracked files (relative to the repository).
+///
+/// [Git]: ./struct.Git.html
+pub fn files() -> Git {
+    Git::new("file", &["ls-files"])
+}
+
+impl Git {
+    fn new(kind: &'static str, args: &[&str]) -> Self {
+        let args = args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        Git {
+            command: Command::new("git").arg("-C").arg("").args(&args),
+            args,
+            kind,
+            skip_head: false,
+            hash_prefix: false,
+            repo_field: None,
+            matcher: Rc::new(SmartCase(Substring)),
+        }
+    }
+
+    /// Sets `path` of the repository (default: current dir).
+    pub fn repo<P: AsRef<Path>>(self, path: P) -> Self {
+        let repo = path.as_ref().to_string_lossy().into_owned();
+        let mut git = self.with_repo(repo);
+        git.repo_field = None;
+        git
+    }
+
+    /// Takes path of the repository from field `label` of the form.
+    ///
+    /// Nothing is suggested (and no error is shown) until the field has a value.
+    ///
+    /// Use it with [Autocomplete::with_context] or [Multiselect::with_context].
+    ///
+    /// [Autocomplete::with_context]: ../../fields/struct.Autocomplete.html#method.with_context
+    /// [Multiselect::with_context]: ../../fields/struct.Multiselect.html#method.with_context
+    pub fn repo_field<IS: Into<String>>(self, label: IS) -> Self {
+        let label = label.into();
+        let repo = format!("{{{}}}", label);
+        let mut git = self.with_repo(repo);
+        git.repo_field = Some(label);
+        git
+    }
+
+    /// Sets [Matcher] filtering and ranking items.
+    ///
+    /// [Matcher]: ../matchers/trait.Matcher.html
+    pub fn matcher<M: Matcher>(mut self, matcher: M) -> Self {
+        self.matcher = Rc::new(matcher);
+        self
+    }
+
+    fn with_repo(mut self, repo: String) -> Self {
+        // `git -C ""` stays in the current dir
+        self.command = Command::new("git")
+            .arg("-C")
+            .arg(repo.as_str())
+            .args(&self.args);
+        self
+    }
+
+    /// Checks if path of repository is known (see `repo_field`).
+    fn has_repo(&self, values: &Map<String, Value>) -> bool {
+        match self.repo_field {
+            Some(ref label) => match values.get(label) {
+                Some(Value::String(repo)) => !repo.is_empty(),
+                Some(Value::Null) | None => false,
+                Some(_) => true,
+            },
+            None => true,
+        }
+    }
+
+    /// Returns all suggestions for repository (found by `values`).
+    fn suggestions(&self, values: &Map<String, Value>) -> Vec<Suggestion> {
+        if !self.has_repo(values) {
+            return Vec::new();
+        }
+        let lines = ContextFeeder::query(&self.command, "", values, 0, usize::MAX);
+        lines
+            .into_iter()
+            .filter(|l| !(self.skip_head && l.ends_with("/HEAD")))
+            .map(|line| {
+                let suggestion = if self.hash_prefix {
+                    let hash = line.split(' ').next().unwrap_or_default().to_string();
+                    Suggestion::new(hash).display(line)
+                } else {
+                    Suggestion::new(line)
+                };
+                suggestion.kind(self.kind)
+            })
+            .collect()
+    }
+}
+
+impl Feeder for Git {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        Feeder::suggest(self, text, position, items_count)
+            .into_iter()
+            .map(|s| s.value)
+            .collect()
+    }
+
+    fn suggest(&self, text: &str, position: usize, items_count: usize) -> Vec<Suggestion> {
+        ContextFeeder::suggest(self, text, &Map::new(), position, items_count)
+    }
+
+    fn cursor(&self, text: &str) -> Option<Cursor> {
+        Some(Cursor::from_vec(Feeder::suggest(self, text, 0, usize::MAX)))
+    }
+
+    fn highlight(&self, text: &str, item: &str) -> Vec<usize> {
+        ContextFeeder::highlight(self, text, &Map::new(), item)
+    }
+
+    fn error(&self, text: &str) -> Option<String> {
+        ContextFeeder::error(self, text, &Map::new())
+    }
+}
+
+/// Path of repository may be taken from values of other fields (see [Git::repo_field]).
+///
+/// [Git::repo_field]: ./struct.Git.html#method.repo_field
+impl ContextFeeder for Git {
+    fn query(
+        &self,
+        text: &str,
+        values: &Map<String, Value>,
+        position: usize,
+        items_count: usize,
+    ) -> Vec<String> {
+        ContextFeeder::suggest(self, text, values, position, items_count)
+            .into_iter()
+            .map(|s| s.value)
+            .collect()
+    }
+
+    fn suggest(
+        &self,
+        text: &str,
+        values: &Map<String, Value>,
+        position: usize,
+        items_count: usize,
+    ) -> Vec<Suggestion> {
+        let items = self.suggestions(values).into_iter();
+        rank_by(
+            &*self.matcher,
+            text,
+            items,
+            |s| &s.display,
+            position,
+            items_count,
+        )
+    }
+
+    fn highlight(&self, text: &str, _values: &Map<String, Value>, item: &str) -> Vec<usize> {
+        self.matcher
+            .find(text, item)
+            .map(|m| m.positions)
+            .unwrap_or_default()
+    }
+
+    fn error(&self, text: &str, values: &Map<String, Value>) -> Option<String> {
+        if !self.has_repo(values) {
+            return None;
+        }
+        ContextFeeder::error(&self.command, text, values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{branches, commits, files, remote_branches, remotes, tags};
+    use feeders::Feeder;
+    use serde_json::map::Map;
+    use serde_json::value::Value;
+    use std::fs;
+    use std::process;
+    use test_utils::TempDir;
+
+    /// Creates repository with 2 commits, branch `feature/x`, tag `v1.0`
+    /// and remote `origin` (with its branch `origin/master`).
+    fn repo() -> TempDir {
+        let dir = TempDir::new("git")
+            .file("README.md", "")
+            .file("src/lib.rs", "");
+        let git = |args: &[&str]| {
+            let status = process::Command::new("git")
+                .arg("-C")
+                .arg(&*dir)
+                .args(["-c", "user.name=fui", "-c", "user.email=fui@example.com"])
+                .args(args)
+                .stdout(process::Stdio::null())
+                .stderr(process::Stdio::null())
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {:?} failed", args);
+        };
+        git(&["init", "-q"]);
+        git(&["checkout", "-q", "-b", "master"]);
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "Add readme"]);
+        fs::write(dir.join("README.md"), "fui").unwrap();
+        git(&["commit", "-q", "-am", "Describe project"]);
+        git(&["branch", "feature/x"]);
+        git(&["tag", "v1.0"]);
+        git(&["remote", "add", "origin", "https://example.com/repo.git"]);
+        git(&["update-ref", "refs/remotes/origin/master", "HEAD"]);
+        git(&[
+            "symbolic-ref",
+            "refs/remotes/origin/HEAD",
+            "refs/remotes/origin/master",
+        ]);
+        dir
+    }
+
+    #[test]
+    fn refs_are_suggested() {
+        let dir = repo();
+        assert_eq!(
+            branches().repo(&dir).query("", 0, 10),
+            vec!["feature/x", "master"]
+        );
+        assert_eq!(
+            branches().repo(&dir).query("feat", 0, 10),
+            vec!["feature/x"]
+        );
+        assert_eq!(
+            remote_branches().repo(&dir).query("", 0, 10),
+            vec!["origin/master"]
+        );
+        assert_eq!(tags().repo(&dir).query("", 0, 10), vec!["v1.0"]);
+        assert_eq!(remotes().repo(&dir).query("", 0, 10), vec!["origin"]);
+        assert_eq!(
+            files().repo(&dir).query("", 0, 10),
+            vec!["README.md", "src/lib.rs"]
+        );
+    }
+
+    #[test]
+    fn commits_insert_short_hashes() {
+        let dir = repo();
+        let found = commits().repo(&dir).suggest("", 0, 10);
+        assert_eq!(found.len(), 2);
+        assert!(found[0].display.ends_with(" Describe project"));
+        assert!(found[0].display.starts_with(&found[0].value));
+        assert_eq!(found[0].kind, "commit");
+        let found = commits().repo(&dir).query("readme", 0, 10);
+        assert_eq!(found, vec![found[0].clone()]);
+        assert!(found[0].len() >= 7);
+    }
+
+    #[test]
+    fn repo_is_taken_from_field() {
+        let dir = repo();
+        let feeder = tags().repo_field("repo");
+        let mut values = Map::new();
+        values.insert("repo".to_string(), Value::from(dir.to_str().unwrap()));
+        assert_eq!(
+            ::feeders::ContextFeeder::query(&feeder, "", &values, 0, 10),
+            vec!["v1.0"]
+        );
+        values.insert("repo".to_string(), Value::from("/missing-repo"));
+        assert!(::feeders::ContextFeeder::error(&feeder, "", &values).is_some());
+    }
+
+    #[test]
+    fn nothing_is_suggested_until_repo_field_has_value() {
+        let feeder = tags().repo_field("repo");
+        let mut values = Map::new();
+        assert!(::feeders::ContextFeeder::suggest(&feeder, "", &values, 0, 10).is_empty());
+        assert_eq!(::feeders::ContextFeeder::error(&feeder, "", &values), None);
+        values.insert("repo".to_string(), Value::from(""));
+        assert!(::feeders::ContextFeeder::suggest(&feeder, "", &values, 0, 10).is_empty());
+        assert_eq!(::feeders::ContextFeeder::error(&feeder, "", &values), None);
+        assert_eq!(feeder.error(""), None);
+    }
+
+    #[test]
+    fn outside_of_repository_is_error() {
+        let feeder = branches().repo("/");
+        assert_eq!(feeder.query("", 0, 10), Vec::<String>::new());
+        assert!(feeder.error("").unwrap().contains("not a git repository"));
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use glob::{MatchOptions, Pattern};
+
+/// Pattern of a `.gitignore` file.
+struct Rule {
+    pattern: Pattern,
+    negated: bool,
+    dir_only: bool,
+    // matched against path relative to dir of `.gitignore` instead of file name
+    anchored: bool,
+}
+
+impl Rule {
+    fn parse(line: &str) -> Option<Rule> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let dir_only = line.ends_with('/');
+        let line = line.trim_end_matches('/');
+        let anchored = line.contains('/');
+        let pattern = Pattern::new(line.trim_start_matches('/')).ok()?;
+        Some(Rule {
+            pattern,
+            negated,
+            dir_only,
+            anchored,
+        })
+    }
+}
+
+/// Decides which paths are ignored by `.gitignore` files of their repositories.
+///
+/// Parsed files are cached, so it's meant to be used for a single listing.
+#[derive(Default)]
+pub(crate) struct Gitignore {
+    rules: HashMap<PathBuf, Vec<Rule>>,
+}
+
+impl Gitignore {
+    /// Returns `true` if `path` or any of its parent dirs is ignored.
+    ///
+    /// Paths outside of git repositories are never ignored.
+    pub(crate) fn is_ignored(&mut self, path: &Path, is_dir: bool) -> bool {
+        let path = match fs::canonicalize(path) {
+            Ok(p) => p,
+            Err(_) => match env::current_dir() {
+                Ok(cwd) => cwd.join(path),
+                Err(_) => return false,
+            },
+        };
+        let root = match path.ancestors().skip(1).find(|d| d.join(".git").exists()) {
+            Some(r) => r.to_path_buf(),
+            None => return false,
+        };
+        let mut current = root.clone();
+        let components = path.strip_prefix(&root).unwrap().components().count();
+        for (idx, component) in path.strip_prefix(&root).unwrap().iter().enumerate() {
+            current.push(component);
+            if component == ".git" {
+                return true;
+            }
+            let current_is_dir = is_dir || idx + 1 < components;
+            if self.matches(&root, &current, current_is_dir) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Checks `path` against `.gitignore` files from `root` to path's dir,
+    /// the last matching rule decides.
+    fn matches(&mut self, root: &Path, path: &Path, is_dir: bool) -> bool {
+        let options = MatchOptions {
+            case_sensitive: true,
+            require_literal_separator: true,
+            require_literal_leading_dot: false,
+        };
+        let mut dirs = path
+            .ancestors()
+            .skip(1)
+            .take_while(|d| d.starts_with(root))
+            .map(Path::to_path_buf)
+            .collect::<Vec<_>>();
+        dirs.reverse();
+
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let mut ignored = false;
+        for dir in dirs {
+            let relative = path
+                .strip_prefix(&dir)
+                .unwrap()
+                .iter()
+                .map(|c| c.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            for rule in self.rules_of(&dir) {
+                if rule.dir_only && !is_dir {
+                    continue;
+                }
+                let candidate = if rule.anchored { &relative } else { &*name };
+                if rule.pattern.matches_with(candidate, &options) {
+                    ignored = !rule.negated;
+                }
+            }
+        }
+        ignored
+    }
+
+    fn rules_of(&mut self, dir: &Path) -> &[Rule] {
+        self.rules.entry(dir.to_path_buf()).or_insert_with(|| {
+            fs::read_to_string(dir.join(".gitignore"))
+                .map(|content| content.lines().filter_map(Rule::parse).collect())
+                .unwrap_or_default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Gitignore;
+    use std::env;
+    use test_utils::TempDir;
+
+    #[test]
+    fn rules_of_nested_files_are_applied() {
+        let repo = TempDir::new("gitignore")
+            .dirs(&[".git", "build/out", "src/gen"])
+            .file(".gitignore", "# comment\n*.log\n!keep.log\n/build/\n")
+            .file("src/.gitignore", "gen/\n");
+
+        let mut gitignore = Gitignore::default();
+        let mut ignored = |path: &str, is_dir| gitignore.is_ignored(&repo.join(path), is_dir);
+        assert!(ignored("debug.log", false));
+        assert!(ignored("src/debug.log", false));
+        assert!(!ignored("keep.log", false));
+        assert!(ignored("build", true));
+        assert!(ignored("build/out/app", false));
+        assert!(ignored("src/gen", true));
+        assert!(!ignored("gen", true));
+        assert!(!ignored("src/lib.rs", false));
+        assert!(ignored(".git", true));
+    }
+
+    #[test]
+    fn paths_outside_of_repository_are_not_ignored() {
+        let mut gitignore = Gitignore::default();
+        assert!(!gitignore.is_ignored(&env::temp_dir().join("x.log"), false));
+    }
+}
@@ -0,0 +1,343 @@
+//! Matchers deciding which suggestions match typed text and how well.
+//!
+//! Matchers are used by feeders working on lists (like [Items]) to filter
+//! and rank suggestions, positions of matched characters are highlighted
+//! by [Autocomplete].
+//!
+//! [Items]: ../struct.Items.html
+//! [Autocomplete]: ../../views/struct.Autocomplete.html
+
+use std::cmp::Reverse;
+
+/// Result of a successful match.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Match {
+    /// How well the candidate matches (higher is better).
+    pub score: i64,
+    /// Positions (in chars) of the candidate's matched characters.
+    pub positions: Vec<usize>,
+}
+
+impl Match {
+    /// Creates a new `Match`.
+    pub fn new(score: i64, positions: Vec<usize>) -> Self {
+        Match { score, positions }
+    }
+}
+
+/// Decides if `candidate` matches `query`.
+pub trait Matcher: 'static {
+    /// Returns `Match` if `candidate` matches `query`, `None` otherwise.
+    fn find(&self, query: &str, candidate: &str) -> Option<Match>;
+}
+
+impl<F> Matcher for F
+where
+    F: Fn(&str, &str) -> Option<Match> + 'static,
+{
+    fn find(&self, query: &str, candidate: &str) -> Option<Match> {
+        self(query, candidate)
+    }
+}
+
+/// Finds char index of `query` in `candidate`.
+fn find_chars(query: &[char], candidate: &[char]) -> Option<usize> {
+    if query.len() > candidate.len() {
+        return None;
+    }
+    (0..candidate.len() - query.len() + 1).find(|&i| candidate[i..i + query.len()] == *query)
+}
+
+/// Matches candidates starting with query (case-sensitive).
+///
+/// Shorter candidates are ranked higher.
+///
+/// ```
+/// # extern crate fui;
+/// # use fui::feeders::matchers::{Matcher, Prefix};
+/// # fn main() {
+/// assert!(Prefix.find("gz", "gzip").is_some());
+/// assert!(Prefix.find("zip", "gzip").is_none());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Prefix;
+
+impl Matcher for Prefix {
+    fn find(&self, query: &str, candidate: &str) -> Option<Match> {
+        let query: Vec<char> = query.chars().collect();
+        let candidate: Vec<char> = candidate.chars().collect();
+        if candidate.starts_with(&query) {
+            let score = -((candidate.len() - query.len()) as i64);
+            Some(Match::new(score, (0..query.len()).collect()))
+        } else {
+            None
+        }
+    }
+}
+
+/// Matches candidates containing query (case-sensitive).
+///
+/// Candidates with earlier occurrence of query are ranked higher.
+///
+/// ```
+/// # extern crate fui;
+/// # use fui::feeders::matchers::{Matcher, Substring};
+/// # fn main() {
+/// assert_eq!(Substring.find("zip", "gzip").unwrap().positions, vec![1, 2, 3]);
+/// assert!(Substring.find("Zip", "gzip").is_none());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Substring;
+
+impl Matcher for Substring {
+    fn find(&self, query: &str, candidate: &str) -> Option<Match> {
+        let query: Vec<char> = query.chars().collect();
+        let candidate: Vec<char> = candidate.chars().collect();
+        find_chars(&query, &candidate)
+            .map(|start| Match::new(-(start as i64), (start..start + query.len()).collect()))
+    }
+}
+
+/// Makes wrapped matcher case-insensitive unless query contains an uppercase char.
+///
+/// ```
+/// # extern crate fui;
+/// # use fui::feeders::matchers::{Matcher, SmartCase, Substring};
+/// # fn main() {
+/// let matcher = SmartCase(Substring);
+/// assert!(matcher.find("gz", "GZip").is_some());
+/// assert!(matcher.find("Gz", "GZip").is_none());
+/// assert!(matcher.find("GZ", "GZip").is_some());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SmartCase<M: Matcher>(pub M);
+
+/// Lowercases `text` char by char (so positions of chars are kept).
+fn lowercase_chars(text: &str) -> String {
+    text.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+impl<M: Matcher> Matcher for SmartCase<M> {
+    fn find(&self, query: &str, candidate: &str) -> Option<Match> {
+        if query.chars().any(|c| c.is_uppercase()) {
+            self.0.find(query, candidate)
+        } else {
+            self.0.find(query, &lowercase_chars(candidate))
+        }
+    }
+}
+
+const SCORE_MATCH: i64 = 16;
+const BONUS_BOUNDARY: i64 = 8;
+const BONUS_CONSECUTIVE: i64 = 8;
+const PENALTY_GAP: i64 = 1;
+const PENALTY_LEADING_MAX: i64 = 3;
+const NONE: i64 = i64::MIN / 2;
+
+/// Checks if char at `idx` starts a word (like `b` in `foo_bar` or `fooBar`).
+fn is_boundary(candidate: &[char], idx: usize) -> bool {
+    if idx == 0 {
+        return true;
+    }
+    let prev = candidate[idx - 1];
+    let current = candidate[idx];
+    !prev.is_alphanumeric() || (prev.is_lowercase() && current.is_uppercase())
+}
+
+/// Matches candidates containing all chars of query in the same order
+/// (case-sensitive, wrap it in [SmartCase] to ignore case).
+///
+/// Consecutive chars and chars starting words are ranked higher,
+/// gaps between matched chars are ranked lower.
+///
+/// [SmartCase]: ./struct.SmartCase.html
+///
+/// ```
+/// # extern crate fui;
+/// # use fui::feeders::matchers::{Fuzzy, Matcher};
+/// # fn main() {
+/// assert_eq!(Fuzzy.find("fb", "foo_bar").unwrap().positions, vec![0, 4]);
+/// assert!(Fuzzy.find("bf", "foo_bar").is_none());
+/// assert!(Fuzzy.find("fb", "foo_bar").unwrap().score > Fuzzy.find("fb", "fxxxxb").unwrap().score);
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Fuzzy;
+
+impl Matcher for Fuzzy {
+    fn find(&self, query: &str, candidate: &str) -> Option<Match> {
+        let query: Vec<char> = query.chars().collect();
+        let candidate: Vec<char> = candidate.chars().collect();
+        if query.is_empty() {
+            return Some(Match::new(0, Vec::new()));
+        }
+        if query.len() > candidate.len() {
+            return None;
+        }
+        let bonus = |j: usize| {
+            if is_boundary(&candidate, j) {
+                SCORE_MATCH + BONUS_BOUNDARY
+            } else {
+                SCORE_MATCH
+            }
+        };
+        // scores[i][j] - best score of query[..=i] with query[i] matched at candidate[j]
+        let mut scores = vec![vec![NONE; candidate.len()]; query.len()];
+        for (j, c) in candidate.iter().enumerate() {
+            if *c == query[0] {
+                scores[0][j] = bonus(j) - PENALTY_GAP * (j as i64).min(PENALTY_LEADING_MAX);
+            }
+        }
+        for i in 1..query.len() {
+            // best score of previous char matched before j (gaps are penalized)
+            let mut gapped = NONE;
+            for j in 1..candidate.len() {
+                let prev = scores[i - 1][j - 1];
+                gapped = (gapped - PENALTY_GAP).max(prev);
+                if candidate[j] == query[i] && gapped > NONE {
+                    let consecutive = if prev > NONE {
+                        prev + BONUS_CONSECUTIVE
+                    } else {
+                        NONE
+                    };
+                    scores[i][j] = bonus(j) + gapped.max(consecutive);
+                }
+            }
+        }
+
+        let last = query.len() - 1;
+        let (mut j, score) = scores[last]
+            .iter()
+            .cloned()
+            .enumerate()
+            .filter(|&(_, s)| s > NONE)
+            .max_by_key(|&(j, s)| (s, -(j as i64)))?;
+
+        let mut positions = vec![j];
+        for i in (0..last).rev() {
+            let target = scores[i + 1][j] - bonus(j);
+            j = (0..j)
+                .rev()
+                .find(|&k| {
+                    let prev = scores[i][k];
+                    prev > NONE
+                        && ((k + 1 == j && prev + BONUS_CONSECUTIVE == target)
+                            || prev - PENALTY_GAP * (j - k - 1) as i64 == target)
+                })
+                .expect("matched position");
+            positions.push(j);
+        }
+        positions.reverse();
+        Some(Match::new(score, positions))
+    }
+}
+
+/// Filters `items` by `matcher`, sorts them by score and returns a page of them.
+pub(crate) fn rank<I>(
+    matcher: &dyn Matcher,
+    text: &str,
+    items: I,
+    position: usize,
+    items_count: usize,
+) -> Vec<String>
+where
+    I: Iterator<Item = String>,
+{
+    rank_by(matcher, text, items, |x| x, position, items_count)
+}
+
+/// Like [rank], but items are matched by their `key`.
+pub(crate) fn rank_by<T, I, K>(
+    matcher: &dyn Matcher,
+    text: &str,
+    items: I,
+    key: K,
+    position: usize,
+    items_count: usize,
+) -> Vec<T>
+where
+    I: Iterator<Item = T>,
+    K: Fn(&T) -> &str,
+{
+    let mut matched: Vec<(i64, T)> = items
+        .filter_map(|x| matcher.find(text, key(&x)).map(|m| (m.score, x)))
+        .collect();
+    // stable sort, items with equal scores keep their order
+    matched.sort_by_key(|&(score, _)| Reverse(score));
+    matched
+        .into_iter()
+        .map(|(_, x)| x)
+        .skip(position)
+        .take(items_count)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_ranks_shorter_higher() {
+        let found = rank(
+            &Prefix,
+            "op",
+            vec!["option", "op", "nop"].into_iter().map(String::from),
+            0,
+            10,
+        );
+        assert_eq!(found, vec!["op", "option"]);
+    }
+
+    #[test]
+    fn fuzzy_prefers_consecutive_and_boundaries() {
+        let found = rank(
+            &Fuzzy,
+            "gz",
+            vec!["gaaaz", "tar.gz", "gzip", "xgz"]
+                .into_iter()
+                .map(String::from),
+            0,
+            10,
+        );
+        assert_eq!(found, vec!["gzip", "tar.gz", "xgz", "gaaaz"]);
+    }
+
+    #[test]
+    fn fuzzy_finds_best_positions() {
+        assert_eq!(Fuzzy.find("ab", "axxxxxab").unwrap().positions, vec![6, 7]);
+        assert_eq!(
+            Fuzzy.find("cfg", "config").unwrap().positions,
+            vec![0, 3, 5]
+        );
+        assert_eq!(
+            Fuzzy.find("", "abc").unwrap().positions,
+            Vec::<usize>::new()
+        );
+        assert!(Fuzzy.find("abcd", "abc").is_none());
+    }
+
+    #[test]
+    fn smart_case_keeps_positions() {
+        // 'İ' lowercases to two chars
+        let m = SmartCase(Fuzzy).find("st", "İstanbul").unwrap();
+        assert_eq!(m.positions, vec![1, 2]);
+    }
+
+    #[test]
+    fn closure_is_matcher() {
+        let exact = |q: &str, c: &str| {
+            if q == c {
+                Some(Match::new(0, (0..c.chars().count()).collect()))
+            } else {
+                None
+            }
+        };
+        assert!(exact.find("a", "a").is_some());
+        assert!(exact.find("a", "ab").is_none());
+    }
+}
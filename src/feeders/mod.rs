@@ -0,0 +1,791 @@
+//! Data providers for `views` with suggestion feature.
+//!
+//! `Views` with suggestion feature:
+//! * [Autocomplete]
+//! * [Multiselect]
+//!
+//! [Autocomplete]: ../views/struct.Autocomplete.html
+//! [Multiselect]: ../views/struct.Multiselect.html
+//!
+//! Feeders working on lists filter and rank their items by [matchers].
+//!
+//! [matchers]: ./matchers/index.html
+//!
+//! Ready-made feeders of system data (like users or hosts) are in [system],
+//! of git repositories (like branches or tags) in [git].
+//!
+//! [system]: ./system/index.html
+//! [git]: ./git/index.html
+//!
+//! Feeders are combined by [chain] and methods of [Feeder] (like [filter]),
+//! combined feeders read their sources lazily by cursors.
+//!
+//! ```
+//! # extern crate fui;
+//! # use fui::feeders::{DirItems, Feeder};
+//! # fn main() {
+//! let archives = DirItems::new()
+//!     .filter(|s| s.value.ends_with(".tar.gz"))
+//!     .with_static_prefix(vec!["~/backups/latest.tar.gz"])
+//!     .dedupe()
+//!     .limit(100);
+//! # }
+//! ```
+//!
+//! [chain]: ./fn.chain.html
+//! [Feeder]: ./trait.Feeder.html
+//! [filter]: ./trait.Feeder.html#method.filter
+
+mod combinators;
+mod command;
+mod context;
+mod cursor;
+mod file;
+pub mod git;
+mod gitignore;
+pub mod matchers;
+mod suggestion;
+pub mod system;
+
+pub use self::combinators::{chain, Chain, Dedupe, Filter, Limit, Map, SortedBy};
+pub use self::command::Command;
+pub use self::context::{ContextFeeder, Contextual};
+pub use self::cursor::{Cursor, Total};
+pub use self::file::FileItems;
+pub use self::suggestion::Suggestion;
+
+use self::gitignore::Gitignore;
+use self::matchers::{rank, rank_by, Matcher, SmartCase, Substring};
+use dirs;
+use glob::{glob_with, MatchOptions};
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Makes data querable.
+///
+/// Views read data by [cursor] if feeder provides it, otherwise page by page
+/// with `query`.
+///
+/// [cursor]: #method.cursor
+pub trait Feeder: 'static {
+    /// Returns data filtered by `text`, `position` limited to `items_count`.
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String>;
+
+    /// Returns [Suggestion]s (values with texts shown in their place)
+    /// filtered by `text`, `position` limited to `items_count`.
+    ///
+    /// By default values returned by `query` are shown as they are.
+    ///
+    /// [Suggestion]: ./struct.Suggestion.html
+    fn suggest(&self, text: &str, position: usize, items_count: usize) -> Vec<Suggestion> {
+        self.query(text, position, items_count)
+            .into_iter()
+            .map(Suggestion::from)
+            .collect()
+    }
+
+    /// Returns [Cursor] reading data filtered by `text` (or `None` if data
+    /// can be read only by `query`, see [Cursor::open]).
+    ///
+    /// It's read once while user scrolls suggestions, instead of querying
+    /// again for every move.
+    ///
+    /// [Cursor]: ./struct.Cursor.html
+    /// [Cursor::open]: ./struct.Cursor.html#method.open
+    fn cursor(&self, _text: &str) -> Option<Cursor> {
+        None
+    }
+
+    /// Returns positions (in chars) of `item`'s characters matching `text`.
+    ///
+    /// Views highlight these characters in suggestions (`item` is suggestion's
+    /// `display`).
+    fn highlight(&self, _text: &str, _item: &str) -> Vec<usize> {
+        Vec::new()
+    }
+
+    /// Returns error which occurred while querying `text` (like a failed command).
+    ///
+    /// Views show it in place of suggestions.
+    fn error(&self, _text: &str) -> Option<String> {
+        None
+    }
+
+    /// Changes suggestions by `map` (like adding descriptions).
+    ///
+    /// `map` may return `Suggestion` or a value (like `String`).
+    fn map<F, T>(self, map: F) -> Map
+    where
+        Self: Sized,
+        F: Fn(Suggestion) -> T + 'static,
+        T: Into<Suggestion>,
+    {
+        Map::new(Rc::new(self), map)
+    }
+
+    /// Suggests only items for which `predicate` returns `true`.
+    fn filter<F>(self, predicate: F) -> Filter
+    where
+        Self: Sized,
+        F: Fn(&Suggestion) -> bool + 'static,
+    {
+        Filter::new(Rc::new(self), predicate)
+    }
+
+    /// Skips suggestions with already suggested values.
+    fn dedupe(self) -> Dedupe
+    where
+        Self: Sized,
+    {
+        Dedupe::new(Rc::new(self))
+    }
+
+    /// Sorts suggestions by `compare`.
+    ///
+    /// All items matching typed text are read before the first one is shown.
+    fn sorted_by<F>(self, compare: F) -> SortedBy
+    where
+        Self: Sized,
+        F: Fn(&Suggestion, &Suggestion) -> Ordering + 'static,
+    {
+        SortedBy::new(Rc::new(self), compare)
+    }
+
+    /// Suggests at most `limit` items.
+    fn limit(self, limit: usize) -> Limit
+    where
+        Self: Sized,
+    {
+        Limit::new(Rc::new(self), limit)
+    }
+
+    /// Suggests `items` (matched like `Vec`'s items) before own items.
+    fn with_static_prefix<T: Display + 'static>(self, items: Vec<T>) -> Chain
+    where
+        Self: Sized,
+    {
+        chain(items, self)
+    }
+}
+
+/// Dummy feeder which does nothing
+#[derive(Clone, Debug)]
+pub struct DummyFeeder;
+impl Feeder for DummyFeeder {
+    fn query(&self, _: &str, _: usize, _: usize) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Suggests items of a list matched by a chosen [Matcher].
+///
+/// ```
+/// # extern crate fui;
+/// # use fui::feeders::{Feeder, Items, Suggestion};
+/// # use fui::feeders::matchers::{Fuzzy, SmartCase};
+/// # fn main() {
+/// let items = Items::new(vec!["bzip2", "gzip", "tar.gz"]).matcher(SmartCase(Fuzzy));
+/// assert_eq!(items.query("gz", 0, 10), vec!["gzip", "tar.gz"]);
+/// assert_eq!(items.highlight("gz", "tar.gz"), vec![4, 5]);
+///
+/// let described = Items::from_suggestions(vec![
+///     Suggestion::new("gzip").description("fast, moderate ratio"),
+///     Suggestion::new("xz").description("slow, high ratio"),
+/// ]);
+/// assert_eq!(described.suggest("gz", 0, 10)[0].description, "fast, moderate ratio");
+/// # }
+/// ```
+///
+/// [Matcher]: ./matchers/trait.Matcher.html
+#[derive(Clone)]
+pub struct Items {
+    items: Vec<Suggestion>,
+    matcher: Rc<dyn Matcher>,
+}
+
+impl Items {
+    /// Creates a new `Items` matching `items` like `Vec` does.
+    pub fn new<T: Display>(items: Vec<T>) -> Self {
+        let items = items.iter().map(|x| format!("{}", x)).collect::<Vec<_>>();
+        Items::from_suggestions(items.into_iter().map(Suggestion::new).collect())
+    }
+
+    /// Creates a new `Items` with [Suggestion]s (matched by their `display`).
+    ///
+    /// [Suggestion]: ./struct.Suggestion.html
+    pub fn from_suggestions(items: Vec<Suggestion>) -> Self {
+        Items {
+            items,
+            matcher: Rc::new(SmartCase(Substring)),
+        }
+    }
+
+    /// Sets [Matcher] filtering and ranking items.
+    ///
+    /// [Matcher]: ./matchers/trait.Matcher.html
+    pub fn matcher<M: Matcher>(mut self, matcher: M) -> Self {
+        self.matcher = Rc::new(matcher);
+        self
+    }
+}
+
+impl Feeder for Items {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        self.suggest(text, position, items_count)
+            .into_iter()
+            .map(|s| s.value)
+            .collect()
+    }
+
+    fn suggest(&self, text: &str, position: usize, items_count: usize) -> Vec<Suggestion> {
+        let items = self.items.iter().cloned();
+        rank_by(
+            &*self.matcher,
+            text,
+            items,
+            |s| &s.display,
+            position,
+            items_count,
+        )
+    }
+
+    fn cursor(&self, text: &str) -> Option<Cursor> {
+        Some(Cursor::from_vec(self.suggest(text, 0, usize::MAX)))
+    }
+
+    fn highlight(&self, text: &str, item: &str) -> Vec<usize> {
+        self.matcher
+            .find(text, item)
+            .map(|m| m.positions)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Debug)]
+enum DirItemType {
+    Dir,
+    All,
+}
+
+/// Query file system for dirs, files, etc.
+///
+/// ```
+/// # extern crate fui;
+/// # use fui::feeders::DirItems;
+/// # fn main() {
+///
+/// // Available in two variants:
+/// let files_and_dirs = DirItems::new(); // suggests files and dirs
+/// let only_dirs = DirItems::dirs(); // suggests only dirs
+///
+/// // Options:
+/// let archives = DirItems::new()
+///     .show_hidden()
+///     .extensions(&["tar.gz", "zip"])
+///     .mark_dirs()
+///     .dirs_first()
+///     .respect_gitignore();
+/// # }
+/// ```
+///
+/// Suggestions have kind `dir` or `file`. Paths which can't be read
+/// (like broken symlinks) are suggested as files.
+#[derive(Clone, Debug)]
+pub struct DirItems {
+    dir_item_type: DirItemType,
+    use_full_paths: bool,
+    show_hidden: bool,
+    extensions: Vec<String>,
+    mark_dirs: bool,
+    dirs_first: bool,
+    respect_gitignore: bool,
+}
+
+impl DirItems {
+    /// Creates a new `DirItems` which suggests files and dirs.
+    pub fn new() -> Self {
+        DirItems {
+            dir_item_type: DirItemType::All,
+            use_full_paths: false,
+            show_hidden: false,
+            extensions: Vec::new(),
+            mark_dirs: false,
+            dirs_first: false,
+            respect_gitignore: false,
+        }
+    }
+    /// Creates a new `DirItems` which suggests only dirs.
+    pub fn dirs() -> Self {
+        DirItems {
+            dir_item_type: DirItemType::Dir,
+            ..DirItems::new()
+        }
+    }
+
+    /// Makes suggestion to be absolute paths (like `/home/user`).
+    pub fn use_full_paths(mut self) -> Self {
+        self.use_full_paths = true;
+        self
+    }
+
+    /// Suggests hidden files and dirs (starting with `.`) too.
+    pub fn show_hidden(mut self) -> Self {
+        self.show_hidden = true;
+        self
+    }
+
+    /// Suggests only files with one of `extensions` (like `rs` or `tar.gz`),
+    /// dirs are suggested regardless of them.
+    pub fn extensions<IS: Into<String> + Clone>(mut self, extensions: &[IS]) -> Self {
+        self.extensions = extensions
+            .iter()
+            .cloned()
+            .map(|e| e.into().trim_start_matches('.').to_string())
+            .collect();
+        self
+    }
+
+    /// Adds trailing `/` to dirs, so picking a dir lists its content.
+    pub fn mark_dirs(mut self) -> Self {
+        self.mark_dirs = true;
+        self
+    }
+
+    /// Suggests dirs before files.
+    ///
+    /// All paths are listed before the first one is shown
+    /// (otherwise they're listed lazily).
+    pub fn dirs_first(mut self) -> Self {
+        self.dirs_first = true;
+        self
+    }
+
+    /// Skips paths ignored by `.gitignore` files (and `.git` dirs) of their repository.
+    pub fn respect_gitignore(mut self) -> Self {
+        self.respect_gitignore = true;
+        self
+    }
+
+    fn has_extension(&self, path: &Path) -> bool {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        self.extensions
+            .iter()
+            .any(|e| name.ends_with(&format!(".{}", e)))
+    }
+
+    /// Returns suggestion of globbed `path` or `None` if it's skipped.
+    fn suggestion(&self, path: PathBuf, gitignore: &mut Gitignore) -> Option<Suggestion> {
+        let is_dir = path.is_dir();
+        let skipped = match self.dir_item_type {
+            DirItemType::Dir => !is_dir,
+            DirItemType::All => {
+                !is_dir && !self.extensions.is_empty() && !self.has_extension(&path)
+            }
+        };
+        if skipped || (self.respect_gitignore && gitignore.is_ignored(&path, is_dir)) {
+            return None;
+        }
+        let path = if self.use_full_paths {
+            fs::canonicalize(&path).unwrap_or(path)
+        } else {
+            path
+        };
+        let mut value = format!("{}", path.display());
+        if is_dir && self.mark_dirs && !value.ends_with('/') {
+            value.push('/');
+        }
+        let kind = if is_dir { "dir" } else { "file" };
+        Some(Suggestion::new(value).kind(kind))
+    }
+}
+
+/// Add star to last component of path.
+fn add_glob<P: AsRef<str>>(path: P) -> String {
+    if path.as_ref().ends_with("/") {
+        return format!("{}*", path.as_ref());
+    }
+    let as_path = Path::new(path.as_ref());
+    if let Some(c) = as_path.components().last() {
+        let last = c.as_os_str().to_str().unwrap();
+        let converted = if !last.contains('*') {
+            let last = if last == "/" {
+                format!("{}*", last)
+            } else {
+                format!("*{}*", last)
+            };
+            as_path.with_file_name(last)
+        } else {
+            as_path.to_path_buf()
+        };
+        format!("{}", converted.display())
+    } else {
+        "*".to_string()
+    }
+}
+
+impl Feeder for DirItems {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        self.suggest(text, position, items_count)
+            .into_iter()
+            .map(|s| s.value)
+            .collect()
+    }
+
+    fn suggest(&self, text: &str, position: usize, items_count: usize) -> Vec<Suggestion> {
+        self.cursor(text)
+            .map(|c| c.skip(position).take(items_count).collect())
+            .unwrap_or_default()
+    }
+
+    /// Paths are globbed lazily, so their total count is unknown
+    /// (unless dirs are suggested first).
+    fn cursor(&self, text: &str) -> Option<Cursor> {
+        let path = if text == "" {
+            format!("./")
+        } else if text.starts_with('~') {
+            match dirs::home_dir() {
+                Some(home) => text.replacen("~", &home.to_string_lossy(), 1),
+                None => text.to_string(),
+            }
+        } else {
+            format!("{}", text)
+        };
+        let path = add_glob(path);
+        let paths = match glob_with(
+            &path,
+            &MatchOptions {
+                case_sensitive: text.chars().any(|c| c.is_uppercase()),
+                require_literal_separator: false,
+                require_literal_leading_dot: !self.show_hidden,
+            },
+        ) {
+            Ok(v) => v,
+            Err(_) => return Some(Cursor::empty()),
+        };
+        let options = self.clone();
+        let mut gitignore = Gitignore::default();
+        let items = paths.filter_map(move |x| match x {
+            Ok(path) => options.suggestion(path, &mut gitignore),
+            Err(e) => {
+                eprintln!("{:?}", e);
+                None
+            }
+        });
+        if self.dirs_first {
+            let mut items = items.collect::<Vec<_>>();
+            items.sort_by_key(|s| s.kind != "dir");
+            return Some(Cursor::from_vec(items));
+        }
+        Some(Cursor::new(items, Total::Unknown))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::fs;
+    use std::iter::FromIterator;
+    use test_utils::TempDir;
+
+    fn expected(start: &str) -> HashSet<String> {
+        let found = {
+            if let Ok(v) = fs::read_dir(start) {
+                v.filter(|x| {
+                    if let Ok(entry) = x.as_ref() {
+                        if let Some(name) = entry.file_name().to_str() {
+                            !name.starts_with(".")
+                        } else {
+                            false
+                        }
+                    } else {
+                        false
+                    }
+                })
+                .map(|x| {
+                    let p = format!("{}", x.as_ref().unwrap().path().display());
+                    p.replace("./", "")
+                })
+                .collect()
+            } else {
+                Vec::new()
+            }
+        };
+        HashSet::<String>::from_iter(found)
+    }
+
+    #[test]
+    fn test_dir_items_position_works_ok() {
+        let di = DirItems::new();
+        assert_eq!(di.query("", 0, 1), vec!["CHANGELOG.md"]);
+        assert_eq!(di.query("", 1, 1), vec!["Cargo.lock"]);
+    }
+
+    #[test]
+    fn test_vec_matches_smart_case_and_ranks() {
+        let items = vec!["tar.gz", "Gzip", "bzip2"];
+        assert_eq!(items.query("Gz", 0, 10), vec!["Gzip"]);
+        assert_eq!(items.query("gz", 0, 10), vec!["Gzip", "tar.gz"]);
+        assert_eq!(items.query("zip", 0, 10), vec!["Gzip", "bzip2"]);
+        assert_eq!(items.highlight("gz", "tar.gz"), vec![4, 5]);
+    }
+
+    #[test]
+    fn test_dir_items_cursor_reads_lazily() {
+        let mut cursor = DirItems::new().cursor("").unwrap();
+        assert_eq!(cursor.total(), Total::Unknown);
+        let file = |value| Some(Suggestion::new(value).kind("file"));
+        assert_eq!(cursor.next(), file("CHANGELOG.md"));
+        assert_eq!(cursor.next(), file("Cargo.lock"));
+    }
+
+    #[test]
+    fn test_vec_cursor_has_total() {
+        let cursor = vec!["tar.gz", "Gzip", "bzip2"].cursor("gz").unwrap();
+        assert_eq!(cursor.total(), Total::Exact(2));
+        let values = cursor.map(|s| s.value).collect::<Vec<String>>();
+        assert_eq!(values, vec!["Gzip", "tar.gz"]);
+    }
+
+    #[derive(Default)]
+    struct Counted {
+        queries: ::std::cell::Cell<usize>,
+    }
+
+    impl Feeder for Counted {
+        fn query(&self, _: &str, position: usize, items_count: usize) -> Vec<String> {
+            self.queries.set(self.queries.get() + 1);
+            (position..45.min(position + items_count))
+                .map(|x| x.to_string())
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_query_only_feeder_is_read_by_pages() {
+        let feeder = Rc::new(Counted::default());
+        let cursor = Cursor::open(&(Rc::clone(&feeder) as Rc<dyn Feeder>), "");
+        assert_eq!(cursor.total(), Total::Unknown);
+        let found = cursor.map(|s| s.value).collect::<Vec<String>>();
+        assert_eq!(found.len(), 45);
+        assert_eq!(found[44], "44");
+        assert_eq!(feeder.queries.get(), 3);
+    }
+
+    #[test]
+    fn test_glob_is_added_ok() {
+        assert_eq!(add_glob(""), "*");
+        assert_eq!(add_glob("/"), "/*");
+        assert_eq!(add_glob("/home/"), "/home/*");
+        assert_eq!(add_glob("/home/user/xxx"), "/home/user/*xxx*");
+        assert_eq!(add_glob("/home/user/*xxx"), "/home/user/*xxx");
+        assert_eq!(add_glob("/home/user/xxx*"), "/home/user/xxx*");
+        assert_eq!(add_glob("**/xxx"), "**/*xxx*");
+        assert_eq!(add_glob("**/*xxx"), "**/*xxx");
+        assert_eq!(add_glob("**/xxx*"), "**/xxx*");
+    }
+
+    #[test]
+    fn test_dir_item_works_with_current_dir() {
+        let di = DirItems::new();
+        let found = di.query("", 0, 100);
+        assert_eq!(HashSet::<String>::from_iter(found), expected("./"));
+    }
+
+    #[test]
+    fn test_dir_item_works_with_current_subdir() {
+        let di = DirItems::new();
+        let found = di.query("examples/", 0, 100);
+        assert_eq!(HashSet::<String>::from_iter(found), expected("./examples"));
+    }
+
+    #[test]
+    fn test_dir_item_works_with_current_missing_dir() {
+        let di = DirItems::new();
+        let found = di.query("missing-dir", 0, 10);
+        assert_eq!(
+            HashSet::<String>::from_iter(found),
+            expected("./missing-dir")
+        );
+    }
+
+    #[test]
+    fn test_dir_item_works_with_homedir() {
+        let di = DirItems::new();
+        let found = di.query("~/", 0, 200);
+        let homedir = dirs::home_dir().unwrap();
+        assert_eq!(
+            HashSet::<String>::from_iter(found),
+            expected(homedir.to_str().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_dir_item_works_with_root_dir() {
+        let di = DirItems::new();
+        let found = di.query("/root", 0, 100);
+        assert_eq!(
+            HashSet::<String>::from_iter(found),
+            HashSet::<String>::from_iter(vec!["/root".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_dir_item_works_with_root_subdir() {
+        let di = DirItems::new();
+        let found = di.query("/root/", 0, 100);
+        assert_eq!(
+            HashSet::<String>::from_iter(found),
+            HashSet::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_dir_item_works_with_top_missing_dir() {
+        let di = DirItems::new();
+        let found = di.query("/missing-dir", 0, 10);
+        assert_eq!(
+            HashSet::<String>::from_iter(found),
+            HashSet::<String>::new()
+        );
+    }
+
+    /// Creates dir with dir `sub`, files `b.tar.gz`, `a.txt`, `.hidden`
+    /// and broken symlink `link` ignoring `*.txt`.
+    fn fixture() -> TempDir {
+        let dir = TempDir::new("dir-items")
+            .dirs(&[".git", "sub"])
+            .file("b.tar.gz", "")
+            .file("a.txt", "")
+            .file(".hidden", "")
+            .file(".gitignore", "*.txt\n");
+        #[cfg(unix)]
+        ::std::os::unix::fs::symlink("/missing-target", dir.join("link")).unwrap();
+        dir
+    }
+
+    /// Returns `dir` as typed text listing its content.
+    fn typed(dir: &TempDir) -> String {
+        format!("{}/", dir.display())
+    }
+
+    fn names(di: &DirItems, dir: &str) -> Vec<String> {
+        di.query(dir, 0, 100)
+            .into_iter()
+            .map(|p| p.replacen(dir, "", 1))
+            .collect()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_dir_items_options() {
+        let tmp = fixture();
+        let dir = typed(&tmp);
+        assert_eq!(
+            names(&DirItems::new(), &dir),
+            vec!["a.txt", "b.tar.gz", "link", "sub"]
+        );
+        assert_eq!(
+            names(&DirItems::new().show_hidden(), &dir),
+            vec![
+                ".git",
+                ".gitignore",
+                ".hidden",
+                "a.txt",
+                "b.tar.gz",
+                "link",
+                "sub"
+            ]
+        );
+        assert_eq!(
+            names(&DirItems::new().extensions(&[".tar.gz"]), &dir),
+            vec!["b.tar.gz", "sub"]
+        );
+        assert_eq!(
+            names(&DirItems::new().mark_dirs().dirs_first(), &dir),
+            vec!["sub/", "a.txt", "b.tar.gz", "link"]
+        );
+        assert_eq!(
+            names(&DirItems::new().show_hidden().respect_gitignore(), &dir),
+            vec![".gitignore", ".hidden", "b.tar.gz", "link", "sub"]
+        );
+        assert_eq!(
+            names(&DirItems::dirs().show_hidden(), &dir),
+            vec![".git", "sub"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_dir_items_suggest_kinds() {
+        let tmp = fixture();
+        let dir = typed(&tmp);
+        let di = DirItems::new().dirs_first().use_full_paths();
+        let cursor = di.cursor(&dir).unwrap();
+        assert_eq!(cursor.total(), Total::Exact(4));
+        let kinds = cursor.map(|s| s.kind).collect::<Vec<_>>();
+        assert_eq!(kinds, vec!["dir", "file", "file", "file"]);
+        // broken symlink can't be canonicalized
+        assert!(di.query(&dir, 3, 1)[0].ends_with("/link"));
+    }
+
+    #[test]
+    fn test_dir_item_works_with_broken_glob() {
+        let di = DirItems::new();
+        let found = di.query("**.", 0, 10);
+        assert_eq!(
+            HashSet::<String>::from_iter(found),
+            HashSet::<String>::new()
+        );
+    }
+}
+
+/// Items are matched case-insensitively (unless typed text contains an
+/// uppercase char) and ranked by position of the match.
+///
+/// Use [Items] to choose other [Matcher].
+///
+/// [Items]: ./struct.Items.html
+/// [Matcher]: ./matchers/trait.Matcher.html
+impl<T: Display + 'static> Feeder for Vec<T> {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        let items = self.iter().map(|x| format!("{}", x));
+        rank(&SmartCase(Substring), text, items, position, items_count)
+    }
+
+    fn cursor(&self, text: &str) -> Option<Cursor> {
+        Some(Cursor::from_vec(self.query(text, 0, usize::MAX)))
+    }
+
+    fn highlight(&self, text: &str, item: &str) -> Vec<usize> {
+        SmartCase(Substring)
+            .find(text, item)
+            .map(|m| m.positions)
+            .unwrap_or_default()
+    }
+}
+
+impl Feeder for Rc<Feeder> {
+    fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+        (**self).query(text, position, items_count)
+    }
+
+    fn suggest(&self, text: &str, position: usize, items_count: usize) -> Vec<Suggestion> {
+        (**self).suggest(text, position, items_count)
+    }
+
+    fn cursor(&self, text: &str) -> Option<Cursor> {
+        (**self).cursor(text)
+    }
+
+    fn highlight(&self, text: &str, item: &str) -> Vec<usize> {
+        (**self).highlight(text, item)
+    }
+
+    fn error(&self, text: &str) -> Option<String> {
+        (**self).error(text)
+    }
+}
@@ -0,0 +1,68 @@
+/// Suggested value with text shown to user in its place.
+///
+/// ```
+/// # extern crate fui;
+/// # use fui::feeders::Suggestion;
+/// # fn main() {
+/// let gzip = Suggestion::new("gzip").description("fast, moderate ratio");
+/// assert_eq!(gzip.value, "gzip");
+/// assert_eq!(gzip.display, "gzip");
+///
+/// let plain: Suggestion = "bzip2".into();
+/// assert_eq!(plain.description, "");
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    /// Value inserted when suggestion is picked.
+    pub value: String,
+    /// Text shown (and matched) in place of value.
+    pub display: String,
+    /// Text shown next to `display` (like help of an option).
+    pub description: String,
+    /// Kind of suggested item (like `action` or `dir`), empty if unknown.
+    pub kind: String,
+}
+
+impl Suggestion {
+    /// Creates a new `Suggestion` showing its `value`.
+    pub fn new<IS: Into<String>>(value: IS) -> Self {
+        let value = value.into();
+        Suggestion {
+            display: value.clone(),
+            value,
+            description: String::new(),
+            kind: String::new(),
+        }
+    }
+
+    /// Sets text shown in place of value.
+    pub fn display<IS: Into<String>>(mut self, display: IS) -> Self {
+        self.display = display.into();
+        self
+    }
+
+    /// Sets text shown next to `display`.
+    pub fn description<IS: Into<String>>(mut self, description: IS) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Sets kind of suggested item.
+    pub fn kind<IS: Into<String>>(mut self, kind: IS) -> Self {
+        self.kind = kind.into();
+        self
+    }
+}
+
+impl From<String> for Suggestion {
+    fn from(value: String) -> Self {
+        Suggestion::new(value)
+    }
+}
+
+impl<'a> From<&'a str> for Suggestion {
+    fn from(value: &'a str) -> Self {
+        Suggestion::new(value)
+    }
+}
@@ -0,0 +1,507 @@
+//! Feeders suggesting data of the local system (Linux).
+//!
+//! They read their sources on every query (so they're always up to date)
+//! and match suggestions like `Vec` does. Sources which can't be read
+//! suggest nothing.
+//!
+//! ```
+//! # extern crate fui;
+//! # use fui::feeders::system::{EnvVars, Users};
+//! # use fui::feeders::Feeder;
+//! # fn main() {
+//! assert!(EnvVars.query("PATH", 0, 10).contains(&"PATH".to_string()));
+//! assert_eq!(Users::new().query("root", 0, 1), vec!["root"]);
+//! # }
+//! ```
+
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use dirs;
+
+use super::matchers::{Matcher, SmartCase, Substring};
+use super::{Cursor, Feeder, Items, Suggestion};
+
+/// Implements `Feeder` for `$feeder` suggesting items of its `suggestions`.
+macro_rules! system_feeder {
+    ($feeder:ident) => {
+        impl Feeder for $feeder {
+            fn query(&self, text: &str, position: usize, items_count: usize) -> Vec<String> {
+                self.suggest(text, position, items_count)
+                    .into_iter()
+                    .map(|s| s.value)
+                    .collect()
+            }
+
+            fn suggest(&self, text: &str, position: usize, items_count: usize) -> Vec<Suggestion> {
+                Items::from_suggestions(self.suggestions()).suggest(text, position, items_count)
+            }
+
+            fn cursor(&self, text: &str) -> Option<Cursor> {
+                Items::from_suggestions(self.suggestions()).cursor(text)
+            }
+
+            fn highlight(&self, text: &str, item: &str) -> Vec<usize> {
+                SmartCase(Substring)
+                    .find(text, item)
+                    .map(|m| m.positions)
+                    .unwrap_or_default()
+            }
+        }
+    };
+}
+
+/// Reads `path` to string (empty if it can't be read).
+fn read(path: &PathBuf) -> String {
+    fs::read_to_string(path).unwrap_or_default()
+}
+
+/// Returns lines of `content` without comments and empty lines.
+fn data_lines(content: &str) -> impl Iterator<Item = &str> {
+    content
+        .lines()
+        .map(|l| l.split('#').next().unwrap().trim())
+        .filter(|l| !l.is_empty())
+}
+
+/// Suggests names of environment variables (described by their values).
+#[derive(Clone, Debug)]
+pub struct EnvVars;
+
+impl EnvVars {
+    fn suggestions(&self) -> Vec<Suggestion> {
+        let mut vars = env::vars_os()
+            .filter_map(|(name, value)| {
+                let name = name.into_string().ok()?;
+                let value = value.to_string_lossy().into_owned();
+                Some(Suggestion::new(name).description(value).kind("env"))
+            })
+            .collect::<Vec<_>>();
+        vars.sort_by(|a, b| a.value.cmp(&b.value));
+        vars
+    }
+}
+
+system_feeder!(EnvVars);
+
+/// Suggests names of local users from `/etc/passwd`
+/// (described by their uid and home dir).
+#[derive(Clone, Debug)]
+pub struct Users {
+    path: PathBuf,
+}
+
+impl Users {
+    /// Creates a new `Users` reading `/etc/passwd`.
+    pub fn new() -> Self {
+        Users::from_file("/etc/passwd")
+    }
+
+    /// Creates a new `Users` reading file in format of `/etc/passwd`.
+    pub fn from_file<P: Into<PathBuf>>(path: P) -> Self {
+        Users { path: path.into() }
+    }
+
+    fn suggestions(&self) -> Vec<Suggestion> {
+        parse_passwd(&read(&self.path))
+    }
+}
+
+impl Default for Users {
+    fn default() -> Self {
+        Users::new()
+    }
+}
+
+system_feeder!(Users);
+
+fn parse_passwd(content: &str) -> Vec<Suggestion> {
+    data_lines(content)
+        .filter_map(|line| {
+            let fields = line.split(':').collect::<Vec<_>>();
+            if fields.len() < 6 {
+                return None;
+            }
+            let description = format!("uid {}, {}", fields[2], fields[5]);
+            Some(
+                Suggestion::new(fields[0])
+                    .description(description)
+                    .kind("user"),
+            )
+        })
+        .collect()
+}
+
+/// Suggests names of local groups from `/etc/group` (described by their gid).
+#[derive(Clone, Debug)]
+pub struct Groups {
+    path: PathBuf,
+}
+
+impl Groups {
+    /// Creates a new `Groups` reading `/etc/group`.
+    pub fn new() -> Self {
+        Groups::from_file("/etc/group")
+    }
+
+    /// Creates a new `Groups` reading file in format of `/etc/group`.
+    pub fn from_file<P: Into<PathBuf>>(path: P) -> Self {
+        Groups { path: path.into() }
+    }
+
+    fn suggestions(&self) -> Vec<Suggestion> {
+        parse_group(&read(&self.path))
+    }
+}
+
+impl Default for Groups {
+    fn default() -> Self {
+        Groups::new()
+    }
+}
+
+system_feeder!(Groups);
+
+fn parse_group(content: &str) -> Vec<Suggestion> {
+    data_lines(content)
+        .filter_map(|line| {
+            let fields = line.split(':').collect::<Vec<_>>();
+            if fields.len() < 3 {
+                return None;
+            }
+            let description = format!("gid {}", fields[2]);
+            Some(
+                Suggestion::new(fields[0])
+                    .description(description)
+                    .kind("group"),
+            )
+        })
+        .collect()
+}
+
+/// Suggests host names from `/etc/hosts` (described by their addresses)
+/// and hosts of `~/.ssh/config`.
+///
+/// Wildcard patterns of ssh config are skipped, each name is suggested once.
+#[derive(Clone, Debug)]
+pub struct Hosts {
+    hosts_file: PathBuf,
+    ssh_config: Option<PathBuf>,
+}
+
+impl Hosts {
+    /// Creates a new `Hosts` reading `/etc/hosts` and `~/.ssh/config`.
+    pub fn new() -> Self {
+        Hosts {
+            hosts_file: PathBuf::from("/etc/hosts"),
+            ssh_config: dirs::home_dir().map(|h| h.join(".ssh/config")),
+        }
+    }
+
+    /// Sets file in format of `/etc/hosts`.
+    pub fn hosts_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.hosts_file = path.into();
+        self
+    }
+
+    /// Sets file in format of `~/.ssh/config`.
+    pub fn ssh_config<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.ssh_config = Some(path.into());
+        self
+    }
+
+    fn suggestions(&self) -> Vec<Suggestion> {
+        let mut hosts = parse_hosts(&read(&self.hosts_file));
+        if let Some(ref ssh_config) = self.ssh_config {
+            hosts.extend(parse_ssh_config(&read(ssh_config)));
+        }
+        let mut seen = HashSet::new();
+        hosts.retain(|s| seen.insert(s.value.clone()));
+        hosts
+    }
+}
+
+impl Default for Hosts {
+    fn default() -> Self {
+        Hosts::new()
+    }
+}
+
+system_feeder!(Hosts);
+
+fn parse_hosts(content: &str) -> Vec<Suggestion> {
+    let mut hosts = Vec::new();
+    for line in data_lines(content) {
+        let mut words = line.split_whitespace();
+        let address = match words.next() {
+            Some(a) => a,
+            None => continue,
+        };
+        for name in words {
+            hosts.push(Suggestion::new(name).description(address).kind("host"));
+        }
+    }
+    hosts
+}
+
+fn parse_ssh_config(content: &str) -> Vec<Suggestion> {
+    let mut hosts: Vec<Suggestion> = Vec::new();
+    // hosts of the current `Host` section
+    let mut section = 0;
+    for line in data_lines(content) {
+        let mut words = line
+            .split(|c: char| c.is_whitespace() || c == '=')
+            .filter(|w| !w.is_empty());
+        let keyword = words.next().unwrap_or_default().to_lowercase();
+        if keyword == "host" {
+            let before = hosts.len();
+            hosts.extend(
+                words
+                    .filter(|w| !w.contains(&['*', '?', '!'][..]))
+                    .map(|w| Suggestion::new(w).description("ssh").kind("host")),
+            );
+            section = hosts.len() - before;
+        } else if keyword == "match" {
+            section = 0;
+        } else if keyword == "hostname" {
+            if let Some(hostname) = words.next() {
+                let start = hosts.len() - section;
+                for host in &mut hosts[start..] {
+                    host.description = format!("ssh {}", hostname);
+                }
+            }
+        }
+    }
+    hosts
+}
+
+/// Suggests network interfaces from `/sys/class/net`
+/// (described by their state and hardware address).
+#[derive(Clone, Debug)]
+pub struct NetworkInterfaces {
+    dir: PathBuf,
+}
+
+impl NetworkInterfaces {
+    /// Creates a new `NetworkInterfaces` reading `/sys/class/net`.
+    pub fn new() -> Self {
+        NetworkInterfaces::from_dir("/sys/class/net")
+    }
+
+    /// Creates a new `NetworkInterfaces` reading dir in format of `/sys/class/net`.
+    pub fn from_dir<P: Into<PathBuf>>(dir: P) -> Self {
+        NetworkInterfaces { dir: dir.into() }
+    }
+
+    fn suggestions(&self) -> Vec<Suggestion> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(e) => e,
+            Err(_) => return Vec::new(),
+        };
+        let mut interfaces = entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let name = e.file_name().into_string().ok()?;
+                let attribute = |a: &str| read(&e.path().join(a)).trim().to_string();
+                let description = [attribute("operstate"), attribute("address")]
+                    .iter()
+                    .filter(|a| !a.is_empty())
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Some(
+                    Suggestion::new(name)
+                        .description(description)
+                        .kind("interface"),
+                )
+            })
+            .collect::<Vec<_>>();
+        interfaces.sort_by(|a, b| a.value.cmp(&b.value));
+        interfaces
+    }
+}
+
+impl Default for NetworkInterfaces {
+    fn default() -> Self {
+        NetworkInterfaces::new()
+    }
+}
+
+system_feeder!(NetworkInterfaces);
+
+/// Suggests mount points of mounted filesystems from `/proc/mounts`
+/// (described by their type and device).
+#[derive(Clone, Debug)]
+pub struct Mounts {
+    path: PathBuf,
+}
+
+impl Mounts {
+    /// Creates a new `Mounts` reading `/proc/mounts`.
+    pub fn new() -> Self {
+        Mounts::from_file("/proc/mounts")
+    }
+
+    /// Creates a new `Mounts` reading file in format of `/proc/mounts`.
+    pub fn from_file<P: Into<PathBuf>>(path: P) -> Self {
+        Mounts { path: path.into() }
+    }
+
+    fn suggestions(&self) -> Vec<Suggestion> {
+        parse_mounts(&read(&self.path))
+    }
+}
+
+impl Default for Mounts {
+    fn default() -> Self {
+        Mounts::new()
+    }
+}
+
+system_feeder!(Mounts);
+
+fn parse_mounts(content: &str) -> Vec<Suggestion> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            if fields.len() < 3 {
+                return None;
+            }
+            let description = format!("{}, {}", fields[2], unescape_octal(fields[0]));
+            Some(
+                Suggestion::new(unescape_octal(fields[1]))
+                    .description(description)
+                    .kind("mount"),
+            )
+        })
+        .collect()
+}
+
+/// Replaces octal escapes (like `\040` for space) used by `/proc/mounts`.
+///
+/// Escaped bytes may be parts of UTF-8 chars, so they're decoded together.
+fn unescape_octal(text: &str) -> String {
+    let mut result = Vec::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(idx) = rest.find('\\') {
+        result.extend_from_slice(&rest.as_bytes()[..idx]);
+        let code = rest
+            .get(idx + 1..idx + 4)
+            .and_then(|c| u8::from_str_radix(c, 8).ok());
+        match code {
+            Some(c) => {
+                result.push(c);
+                rest = &rest[idx + 4..];
+            }
+            None => {
+                result.push(b'\\');
+                rest = &rest[idx + 1..];
+            }
+        }
+    }
+    result.extend_from_slice(rest.as_bytes());
+    String::from_utf8_lossy(&result).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_utils::TempDir;
+
+    fn described(found: Vec<Suggestion>) -> Vec<(String, String)> {
+        found
+            .into_iter()
+            .map(|s| (s.value, s.description))
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected
+            .iter()
+            .map(|&(v, d)| (v.to_string(), d.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn passwd_and_group_are_parsed() {
+        let passwd = "root:x:0:0:root:/root:/bin/bash\n# comment\nbroken\nann:x:1000:1000:Ann,,,:/home/ann:/bin/sh\n";
+        assert_eq!(
+            described(parse_passwd(passwd)),
+            pairs(&[("root", "uid 0, /root"), ("ann", "uid 1000, /home/ann")])
+        );
+        let group = "root:x:0:\nwheel:x:10:ann,bob\n";
+        assert_eq!(
+            described(parse_group(group)),
+            pairs(&[("root", "gid 0"), ("wheel", "gid 10")])
+        );
+    }
+
+    #[test]
+    fn hosts_and_ssh_config_are_parsed() {
+        let hosts = "127.0.0.1 localhost\n::1 ip6-localhost ip6-loopback # comment\n\n";
+        assert_eq!(
+            described(parse_hosts(hosts)),
+            pairs(&[
+                ("localhost", "127.0.0.1"),
+                ("ip6-localhost", "::1"),
+                ("ip6-loopback", "::1"),
+            ])
+        );
+        let ssh_config = "Host *\n  User ann\nHost web web2\n  HostName 10.0.0.5\nHost=db\nMatch all\n  HostName ignored\n";
+        assert_eq!(
+            described(parse_ssh_config(ssh_config)),
+            pairs(&[
+                ("web", "ssh 10.0.0.5"),
+                ("web2", "ssh 10.0.0.5"),
+                ("db", "ssh"),
+            ])
+        );
+    }
+
+    #[test]
+    fn hosts_are_suggested_once() {
+        let dir = TempDir::new("hosts")
+            .file("hosts", "10.0.0.5 web\n")
+            .file("config", "Host web db\n");
+        let feeder = Hosts::new()
+            .hosts_file(dir.join("hosts"))
+            .ssh_config(dir.join("config"));
+        assert_eq!(
+            described(feeder.suggest("", 0, 10)),
+            pairs(&[("web", "10.0.0.5"), ("db", "ssh")])
+        );
+    }
+
+    #[test]
+    fn mounts_are_parsed() {
+        let mounts = "proc /proc proc rw 0 0\n/dev/sda1 /mnt/my\\040disk ext4 rw 0 0\n";
+        assert_eq!(
+            described(parse_mounts(mounts)),
+            pairs(&[("/proc", "proc, proc"), ("/mnt/my disk", "ext4, /dev/sda1")])
+        );
+        assert_eq!(unescape_octal("a\\134b\\"), "a\\b\\");
+        assert_eq!(unescape_octal("/mnt/\\303\\251t\\303\\251"), "/mnt/été");
+    }
+
+    #[test]
+    fn missing_sources_suggest_nothing() {
+        assert_eq!(
+            Users::from_file("/missing").query("", 0, 10),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            NetworkInterfaces::from_dir("/missing").query("", 0, 10),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn env_vars_are_described_by_values() {
+        env::set_var("FUI_SYSTEM_TEST", "value");
+        let found = EnvVars.suggest("FUI_SYSTEM_TEST", 0, 10);
+        assert_eq!(described(found), pairs(&[("FUI_SYSTEM_TEST", "value")]));
+    }
+}
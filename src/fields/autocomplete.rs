@@ -2,9 +2,10 @@ use std::rc::Rc;
 
 use clap;
 use cursive::views::ViewBox;
+use serde_json::map::Map;
 use serde_json::value::Value;
 
-use feeders::{DummyFeeder, Feeder};
+use feeders::{ContextFeeder, Contextual, DummyFeeder, Feeder};
 use fields;
 use fields::{FieldErrors, WidgetManager};
 use views;
@@ -18,7 +19,21 @@ impl Autocomplete {
         label: IS,
         feeder: F,
     ) -> fields::Field<AutocompleteManager, String> {
-        fields::Field::new(label, AutocompleteManager::with_feeder(feeder), "".to_string())
+        fields::Field::new(
+            label,
+            AutocompleteManager::with_feeder(feeder),
+            "".to_string(),
+        )
+    }
+
+    /// Creates a new `Field<AutocompleteManager, String>` with suggestions
+    /// depending on values of other fields.
+    pub fn with_context<IS: Into<String>, F: ContextFeeder>(
+        label: IS,
+        feeder: F,
+    ) -> fields::Field<AutocompleteManager, String> {
+        let mngr = AutocompleteManager::with_context_feeder(feeder);
+        fields::Field::new(label, mngr, "".to_string())
     }
 }
 
@@ -26,6 +41,7 @@ impl Autocomplete {
 pub struct AutocompleteManager {
     feeder: Rc<Feeder>,
     view_factory: Option<Rc<Fn() -> views::Autocomplete>>,
+    context: Option<Contextual>,
 }
 
 impl AutocompleteManager {
@@ -41,6 +57,19 @@ impl AutocompleteManager {
         AutocompleteManager {
             feeder: Rc::new(feeder),
             view_factory: None,
+            context: None,
+        }
+    }
+
+    /// Creates an instance with a [ContextFeeder] (which gets values of other fields).
+    ///
+    /// [ContextFeeder]: ../../feeders/trait.ContextFeeder.html
+    pub fn with_context_feeder<T: ContextFeeder>(feeder: T) -> Self {
+        let context = Contextual::new(feeder);
+        AutocompleteManager {
+            feeder: Rc::new(context.clone()),
+            view_factory: None,
+            context: Some(context),
         }
     }
     /// Creates an instance with customized [views::Autocomplete].
@@ -55,6 +84,7 @@ impl AutocompleteManager {
         AutocompleteManager {
             feeder: Rc::new(DummyFeeder),
             view_factory: Some(factory),
+            context: None,
         }
     }
 
@@ -91,17 +121,13 @@ impl fields::FormField for fields::Field<AutocompleteManager, String> {
         &self.widget_manager
     }
     fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
-        let mut errors = FieldErrors::new();
-        for v in &self.validators {
-            if let Some(e) = v.validate(data) {
-                errors.push(e);
-            }
-        }
-        if errors.len() > 0 {
-            Err(errors)
-        } else {
-            Ok(Value::String(data.to_owned()))
-        }
+        self.convert_and_validate(data)
+    }
+
+    validate_in_context!();
+
+    fn warnings(&self, data: &str, values: &Map<String, Value>) -> FieldErrors {
+        self.run_warnings(data, values)
     }
 
     /// Gets label of the field
@@ -130,7 +156,29 @@ impl fields::FormField for fields::Field<AutocompleteManager, String> {
         args.value_of(&self.label).unwrap_or("").to_string()
     }
 
+    fn dump_as_cli(&self, value: &Value) -> Vec<String> {
+        self.dump_converted(value)
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        self.revert(value)
+    }
+
     fn is_required(&self) -> bool {
         self.is_required()
     }
+
+    fn uses_context(&self) -> bool {
+        self.widget_manager.context.is_some()
+    }
+
+    fn set_context(&self, viewbox: &mut ViewBox, values: &Map<String, Value>) {
+        if let Some(ref context) = self.widget_manager.context {
+            context.set_values(values.clone());
+            let view_box = fields::value_view_from_layout_mut(viewbox);
+            let autocomplete: &mut views::Autocomplete =
+                (**view_box).as_any_mut().downcast_mut().unwrap();
+            autocomplete.refresh();
+        }
+    }
 }
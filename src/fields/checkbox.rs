@@ -2,10 +2,13 @@ use std::str::FromStr;
 
 use clap;
 use cursive::views;
+use serde_json::map::Map;
 use serde_json::value::Value;
 
 use fields;
 use fields::{FieldErrors, WidgetManager};
+use form::Diagnostic;
+use messages::Message;
 
 /// Convienient wrapper around `Field<CheckboxManager, bool>`.
 pub struct Checkbox;
@@ -58,15 +61,16 @@ impl fields::FormField for fields::Field<CheckboxManager, bool> {
     }
 
     fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
-        let result = FromStr::from_str(data)
+        let value = FromStr::from_str(data)
             .map(|v| Value::Bool(v))
-            .map_err(|_| {
-                let mut errors = FieldErrors::new();
-                let error = "Value can't be converterd to bool".to_string();
-                errors.push(error);
-                errors
-            });
-        result
+            .map_err(|_| vec![Message::new("checkbox.not_bool").into()])?;
+        self.convert_value(value).map_err(|e| vec![e])
+    }
+
+    validate_in_context!();
+
+    fn warnings(&self, data: &str, values: &Map<String, Value>) -> FieldErrors {
+        self.run_warnings(data, values)
     }
 
     fn clap_arg(&self) -> clap::Arg {
@@ -84,9 +88,27 @@ impl fields::FormField for fields::Field<CheckboxManager, bool> {
         v.to_string()
     }
 
+    fn dump_as_cli(&self, value: &Value) -> Vec<String> {
+        let mut map = Map::with_capacity(1);
+        map.insert(self.label.clone(), self.revert_value(value));
+        ::value2array(&Value::Object(map))
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        self.revert(value)
+    }
+
     fn is_required(&self) -> bool {
         self.is_required()
     }
+
+    fn check(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = fields::check_initial(self);
+        if self.is_required() {
+            diagnostics.push(Diagnostic::new(Message::new("check.required_checkbox")));
+        }
+        diagnostics
+    }
 }
 
 impl<W: WidgetManager> fields::Field<W, bool> {
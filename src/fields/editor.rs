@@ -0,0 +1,122 @@
+use clap;
+use cursive::views;
+use serde_json::map::Map;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+use views::Editor as EditorView;
+
+/// Convienient wrapper around `Field<EditorManager, String>`.
+///
+/// Form shows only a summary of the value, which is edited in external
+/// editor (`$VISUAL` or `$EDITOR`). It fits long or structured input like
+/// YAML snippets, templates, etc.
+pub struct Editor;
+
+impl Editor {
+    /// Creates a new `Field<EditorManager, String>`.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<EditorManager, String> {
+        let mngr = EditorManager {
+            extension: "".to_string(),
+        };
+        fields::Field::new(label, mngr, "".to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct EditorManager {
+    extension: String,
+}
+
+impl WidgetManager for EditorManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::ViewBox {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::ViewBox) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let editor: &EditorView = (**view_box).as_any().downcast_ref().unwrap();
+        editor.get_value()
+    }
+    fn build_value_view(&self, value: &str) -> views::ViewBox {
+        let editor = EditorView::new(value).extension(self.extension.as_str());
+        views::ViewBox::new(Box::new(editor))
+    }
+}
+
+impl fields::Field<EditorManager, String> {
+    /// Sets extension of edited file (e.g. `yaml`), so editor can highlight syntax.
+    pub fn extension<IS: Into<String>>(mut self, extension: IS) -> Self {
+        self.widget_manager.extension = extension.into();
+        self
+    }
+}
+
+impl fields::FormField for fields::Field<EditorManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        self.convert_and_validate(data)
+    }
+
+    validate_in_context!();
+
+    fn warnings(&self, data: &str, values: &Map<String, Value>) -> FieldErrors {
+        self.run_warnings(data, values)
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg<'_, '_> {
+        clap::Arg::with_name(&self.label)
+            .long(&self.label)
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(&self.label).unwrap_or("").to_string()
+    }
+
+    fn dump_as_cli(&self, value: &Value) -> Vec<String> {
+        self.dump_converted(value)
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        self.revert(value)
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fields::FormField;
+
+    #[test]
+    fn multiline_value_is_taken_from_widget() {
+        let field = Editor::new("config").initial("a: 1\nb: 2\n");
+        let widget = field.build_widget();
+        let value = field.get_widget_manager().get_value(&widget);
+        assert_eq!(field.validate(&value), Ok(json!("a: 1\nb: 2\n")));
+    }
+}
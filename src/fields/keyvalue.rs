@@ -0,0 +1,331 @@
+use std::rc::Rc;
+
+use clap;
+use cursive::views::ViewBox;
+use serde_json::map::Map;
+use serde_json::value::Value;
+
+use fields;
+use fields::{Field, FieldErrors, FormField, WidgetManager};
+use messages::Message;
+use validators::Validator;
+use views;
+
+const PAIR_SEP: &str = "\n";
+
+/// Convienient wrapper around `Field<KeyValueManager, Vec<(String, String)>>`.
+///
+/// `KeyValue` covers repeated options like `--env KEY=VALUE --env KEY2=VALUE2`.
+pub struct KeyValue;
+
+impl KeyValue {
+    /// Creates a new `Field<KeyValueManager, Vec<(String, String)>>`.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<IS: Into<String>>(label: IS) -> Field<KeyValueManager, Vec<(String, String)>> {
+        let mngr = KeyValueManager {
+            as_object: false,
+            key_validators: Vec::new(),
+            value_validators: Vec::new(),
+        };
+        Field::new(label, mngr, Vec::new())
+    }
+}
+
+#[derive(Clone)]
+pub struct KeyValueManager {
+    as_object: bool,
+    key_validators: Vec<Rc<dyn Validator>>,
+    value_validators: Vec<Rc<dyn Validator>>,
+}
+
+/// Splits `data` to pairs, each pair is in format `KEY=VALUE`.
+fn str2pairs(data: &str) -> Result<Vec<(String, String)>, FieldErrors> {
+    let mut pairs = Vec::new();
+    let mut errors = FieldErrors::new();
+    for line in data.split(PAIR_SEP).filter(|x| !x.is_empty()) {
+        match line.find('=') {
+            Some(idx) => pairs.push((line[..idx].to_string(), line[idx + 1..].to_string())),
+            None => {
+                let msg = Message::new("keyvalue.not_pair").param("value", format!("{:?}", line));
+                errors.push(msg.into());
+            }
+        }
+    }
+    if !errors.is_empty() {
+        Err(errors)
+    } else {
+        Ok(pairs)
+    }
+}
+
+fn pairs2str(pairs: &[(String, String)]) -> String {
+    pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<String>>()
+        .join(PAIR_SEP)
+}
+
+impl WidgetManager for KeyValueManager {
+    fn build_value_view(&self, initial: &str) -> ViewBox {
+        let pairs = str2pairs(initial).unwrap_or_default();
+        ViewBox::new(Box::new(views::KeyValue::new().pairs(pairs)))
+    }
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> ViewBox {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &ViewBox) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let kv: &views::KeyValue = (**view_box).as_any().downcast_ref().unwrap();
+        pairs2str(&kv.get_pairs())
+    }
+}
+
+impl FormField for Field<KeyValueManager, Vec<(String, String)>> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let (mut errors, _) = self.run_validators(data);
+        let pairs = match str2pairs(data) {
+            Ok(pairs) => pairs,
+            Err(mut e) => {
+                errors.append(&mut e);
+                return Err(errors);
+            }
+        };
+        errors.extend(
+            self.check_pairs(&pairs)
+                .into_iter()
+                .filter(|e| !e.is_warning()),
+        );
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        let value = if self.widget_manager.as_object {
+            let mut map = Map::with_capacity(pairs.len());
+            for (key, value) in pairs.into_iter() {
+                map.insert(key, Value::String(value));
+            }
+            Value::Object(map)
+        } else {
+            let items = pairs
+                .iter()
+                .map(|(k, v)| Value::String(format!("{}={}", k, v)))
+                .collect();
+            Value::Array(items)
+        };
+        self.convert_value(value).map_err(|e| vec![e])
+    }
+
+    validate_in_context!();
+
+    fn warnings(&self, data: &str, values: &Map<String, Value>) -> FieldErrors {
+        let mut warnings = self.run_warnings(data, values);
+        if let Ok(pairs) = str2pairs(data) {
+            warnings.extend(
+                self.check_pairs(&pairs)
+                    .into_iter()
+                    .filter(|e| e.is_warning()),
+            );
+        }
+        warnings
+    }
+
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        pairs2str(&self.initial)
+    }
+
+    fn clap_arg(&self) -> clap::Arg<'_, '_> {
+        clap::Arg::with_name(&self.label)
+            .long(&self.label)
+            .help(&self.help)
+            .required(self.is_required())
+            .multiple(true)
+            .number_of_values(1)
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        let values = args.values_of(&self.label).unwrap_or_default();
+        values.collect::<Vec<&str>>().join(PAIR_SEP)
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        self.value2pairs(value).join(PAIR_SEP)
+    }
+
+    fn dump_as_cli(&self, value: &Value) -> Vec<String> {
+        let pairs = self.value2pairs(value);
+        let mut args = Vec::with_capacity(pairs.len() * 2);
+        for pair in pairs {
+            args.push(format!("--{}", self.label));
+            args.push(pair);
+        }
+        args
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+}
+
+impl<W: WidgetManager> Field<W, Vec<(String, String)>> {
+    /// Sets initial `value` of `field`.
+    pub fn initial<K: Into<String>, V: Into<String>>(mut self, initial: Vec<(K, V)>) -> Self {
+        self.initial = initial
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        self
+    }
+}
+
+impl Field<KeyValueManager, Vec<(String, String)>> {
+    /// Makes the field's value a JSON object (instead of `["KEY=VALUE", ...]`).
+    pub fn as_object(mut self) -> Self {
+        self.widget_manager.as_object = true;
+        self
+    }
+
+    /// Appends `validator` checking each key.
+    ///
+    /// Its warnings (see [Warn]) don't block submitting, like field's ones.
+    ///
+    /// [Warn]: ../validators/struct.Warn.html
+    pub fn key_validator<V: Validator + 'static>(mut self, validator: V) -> Self {
+        self.widget_manager.key_validators.push(Rc::new(validator));
+        self
+    }
+
+    /// Appends `validator` checking each value.
+    pub fn value_validator<V: Validator + 'static>(mut self, validator: V) -> Self {
+        self.widget_manager
+            .value_validators
+            .push(Rc::new(validator));
+        self
+    }
+
+    /// Converts `value` (result of `validate`) back to pairs in format `KEY=VALUE`.
+    fn value2pairs(&self, value: &Value) -> Vec<String> {
+        match self.revert_value(value) {
+            Value::Object(map) => map
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v.as_str().unwrap_or("")))
+                .collect(),
+            Value::Array(items) => items
+                .iter()
+                .filter_map(|v| v.as_str().map(|x| x.to_string()))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Runs key & value validators on `pairs` returning their errors and warnings.
+    fn check_pairs(&self, pairs: &[(String, String)]) -> FieldErrors {
+        let mut found = FieldErrors::new();
+        for (key, value) in pairs.iter() {
+            for v in &self.widget_manager.key_validators {
+                if let Some(mut e) = v.error(key) {
+                    let msg = Message::new("keyvalue.key_error")
+                        .param("key", format!("{:?}", key))
+                        .param("error", &e.message);
+                    e.message = msg.to_string();
+                    found.push(e);
+                }
+            }
+            for v in &self.widget_manager.value_validators {
+                if let Some(mut e) = v.error(value) {
+                    let msg = Message::new("keyvalue.value_error")
+                        .param("key", format!("{:?}", key))
+                        .param("error", &e.message);
+                    e.message = msg.to_string();
+                    found.push(e);
+                }
+            }
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use converters::Lowercase;
+    use validators::{Required, Warn};
+
+    #[test]
+    fn key_value_is_validated_to_array_by_default() {
+        let field = KeyValue::new("env");
+        let exp: Value = serde_json::from_str(r#"["A=1", "B=x=y"]"#).unwrap();
+        assert_eq!(field.validate("A=1\nB=x=y"), Ok(exp));
+    }
+
+    #[test]
+    fn converters_run_on_pairs() {
+        let field = KeyValue::new("env").converter(Lowercase);
+        let exp: Value = serde_json::from_str(r#"["a=x", "b=y"]"#).unwrap();
+        assert_eq!(field.validate("A=X\nB=y"), Ok(exp.clone()));
+        assert_eq!(
+            field.dump_as_cli(&exp),
+            vec!["--env", "a=x", "--env", "b=y"]
+        );
+    }
+
+    #[test]
+    fn key_value_is_validated_to_object_when_set() {
+        let field = KeyValue::new("env").as_object();
+        let exp: Value = serde_json::from_str(r#"{"A": "1", "B": ""}"#).unwrap();
+        assert_eq!(field.validate("A=1\nB="), Ok(exp));
+    }
+
+    #[test]
+    fn key_value_reports_malformed_pairs() {
+        let field = KeyValue::new("env");
+        assert_eq!(
+            field.validate("A").unwrap_err(),
+            vec!["Value \"A\" must be in format KEY=VALUE".to_string()]
+        );
+    }
+
+    #[test]
+    fn key_value_runs_key_and_value_validators() {
+        let field = KeyValue::new("env")
+            .key_validator(regex::Regex::new("^[A-Z]+$").unwrap())
+            .value_validator(Required);
+        let errors = field.validate("a=1\nB=").unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].message.starts_with("Key \"a\""));
+        assert_eq!(errors[1], "Value of \"B\": Field is required");
+    }
+
+    #[test]
+    fn key_value_warnings_dont_make_it_invalid() {
+        let field = KeyValue::new("env").value_validator(Warn(Required));
+        let exp: Value = serde_json::from_str(r#"["A=1", "B="]"#).unwrap();
+        assert_eq!(field.validate("A=1\nB="), Ok(exp));
+        let warnings = field.warnings("A=1\nB=", &Map::new());
+        assert_eq!(warnings, vec!["Value of \"B\": Field is required"]);
+        assert!(warnings[0].is_warning());
+    }
+
+    #[test]
+    fn key_value_is_dumped_as_repeated_option() {
+        let field = KeyValue::new("env");
+        let value: Value = serde_json::from_str(r#"["A=1", "B=2"]"#).unwrap();
+        assert_eq!(
+            field.dump_as_cli(&value),
+            vec!["--env", "A=1", "--env", "B=2"]
+        );
+    }
+}
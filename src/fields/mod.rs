@@ -1,20 +1,50 @@
 //! Includes `form's` building blocks, `fields`.
 use clap;
+use converters::Converter;
+use cursive::theme::{BaseColor, Color};
+use cursive::utils::markup::StyledString;
 use cursive::view::View;
 use cursive::views;
+use form::Diagnostic;
+use messages::Message;
+use serde_json::map::Map;
 use serde_json::value::Value;
 use std::rc::Rc;
-use validators::{Required, Validator};
+use validators;
+use validators::{ContextValidator, ValidationError, Validator};
+
+/// Implements `FormField::validate_in_context` by running `Field`'s context validators.
+macro_rules! validate_in_context {
+    () => {
+        fn validate_in_context(
+            &self,
+            data: &str,
+            values: &Map<String, Value>,
+        ) -> Result<(), FieldErrors> {
+            self.run_context_validators(data, values)
+        }
+    };
+}
 
 pub(crate) mod autocomplete;
 mod checkbox;
+mod editor;
+pub(crate) mod keyvalue;
 pub(crate) mod multiselect;
+mod path;
+mod subform;
 mod text;
+mod toggle;
 
 pub use self::autocomplete::Autocomplete;
 pub use self::checkbox::Checkbox;
+pub use self::editor::Editor;
+pub use self::keyvalue::KeyValue;
 pub use self::multiselect::Multiselect;
+pub use self::path::Path;
+pub use self::subform::{RecordErrors, SubForm};
 pub use self::text::Text;
+pub use self::toggle::Toggle;
 
 /// Covers communication from [Field] to [View].
 ///
@@ -67,6 +97,8 @@ pub struct Field<W: WidgetManager, T> {
     help: String,
     initial: T,
     validators: Vec<Rc<Validator>>,
+    converters: Vec<Rc<dyn Converter>>,
+    context_validators: Vec<Rc<dyn ContextValidator>>,
     widget_manager: W,
 }
 
@@ -82,6 +114,8 @@ impl<W: WidgetManager, T> Field<W, T> {
             help: "".into(),
             initial: initial,
             validators: vec![],
+            converters: vec![],
+            context_validators: vec![],
             widget_manager: widget_manager,
         }
     }
@@ -95,16 +129,163 @@ impl<W: WidgetManager, T> Field<W, T> {
         self.validators.push(Rc::new(validator));
         self
     }
+    /// Append `validator` which gets values of other fields too.
+    ///
+    /// It runs when all fields are validated, only if this field is valid.
+    pub fn context_validator<V: ContextValidator + 'static>(mut self, validator: V) -> Self {
+        self.context_validators.push(Rc::new(validator));
+        self
+    }
+    /// Append `converter`.
+    ///
+    /// Converters run (in order of appending) before validators. Fields with
+    /// structured values (`Checkbox`, `Toggle`, `KeyValue`, `SubForm`) run them
+    /// on already parsed and validated value (like `true` or a JSON object).
+    pub fn converter<C: Converter + 'static>(mut self, converter: C) -> Self {
+        self.converters.push(Rc::new(converter));
+        self
+    }
     /// Checks if Field is required
     pub fn is_required(&self) -> bool {
         self.validators
             .iter()
-            .any(|&ref x| (**x).as_any().downcast_ref::<Required>().is_some())
+            .any(|x| validators::is_required(&**x))
+    }
+    /// Describes constraints of field's validators (e.g. for help message).
+    pub fn constraints(&self) -> Vec<String> {
+        self.validators
+            .iter()
+            .filter_map(|v| v.describe())
+            .collect()
+    }
+    /// Runs context validators on `data`.
+    fn run_context_validators(
+        &self,
+        data: &str,
+        values: &Map<String, Value>,
+    ) -> Result<(), FieldErrors> {
+        let errors = self
+            .context_validators
+            .iter()
+            .filter_map(|v| v.validate(data, values))
+            .filter(|e| !e.is_warning())
+            .collect::<FieldErrors>();
+        if !errors.is_empty() {
+            Err(errors)
+        } else {
+            Ok(())
+        }
+    }
+    /// Runs converters on `data`.
+    fn convert(&self, data: &str) -> Result<Value, ValidationError> {
+        self.convert_value(Value::String(data.to_string()))
+    }
+    /// Runs converters on `value`.
+    ///
+    /// Fields with structured values (like `Checkbox` or `SubForm`) pass
+    /// them already parsed.
+    fn convert_value(&self, mut value: Value) -> Result<Value, ValidationError> {
+        for c in self.converters.iter() {
+            value = c.convert(value)?;
+        }
+        Ok(value)
+    }
+    /// Converts `value` back to a form accepted by converters.
+    fn revert_value(&self, value: &Value) -> Value {
+        let mut value = value.clone();
+        for c in self.converters.iter().rev() {
+            value = c.revert(value);
+        }
+        value
+    }
+    /// Converts `value` back to text accepted by converters.
+    fn revert(&self, value: &Value) -> String {
+        match self.revert_value(value) {
+            Value::String(s) => s,
+            Value::Null => "".to_string(),
+            other => other.to_string(),
+        }
+    }
+    /// Converts items of array `value` back to text accepted by converters, joined by `sep`.
+    fn revert_items(&self, value: &Value, sep: &str) -> String {
+        value
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .map(|x| self.revert(x))
+                    .collect::<Vec<String>>()
+                    .join(sep)
+            })
+            .unwrap_or_default()
+    }
+    /// Runs converters on `data` and validators on its result.
+    ///
+    /// Validators get converted value in its text form (see `revert`),
+    /// so it's validated what's dumped to CLI.
+    fn convert_and_validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let value = self.convert(data).map_err(|e| vec![e])?;
+        let text = self.revert(&value);
+        let (errors, _) = self.run_validators(&text);
+        if !errors.is_empty() {
+            Err(errors)
+        } else {
+            Ok(value)
+        }
+    }
+    /// Runs validators on `text` returning its errors and warnings.
+    fn run_validators(&self, text: &str) -> (FieldErrors, FieldErrors) {
+        self.validators
+            .iter()
+            .filter_map(|v| v.error(text))
+            .partition(|e| !e.is_warning())
+    }
+    /// Collects warnings of validators for `data` (converted like in `convert_and_validate`).
+    fn item_warnings(&self, data: &str) -> FieldErrors {
+        match self.convert(data) {
+            Ok(value) => self.run_validators(&self.revert(&value)).1,
+            Err(_) => Vec::new(),
+        }
+    }
+    /// Collects warnings of context validators for `data`.
+    fn context_warnings(&self, data: &str, values: &Map<String, Value>) -> FieldErrors {
+        self.context_validators
+            .iter()
+            .filter_map(|v| v.validate(data, values))
+            .filter(|e| e.is_warning())
+            .collect()
+    }
+    /// Collects all warnings for `data` of a single value.
+    fn run_warnings(&self, data: &str, values: &Map<String, Value>) -> FieldErrors {
+        let mut warnings = self.item_warnings(data);
+        warnings.extend(self.context_warnings(data, values));
+        warnings
+    }
+    /// Dumps converted `value` to CLI as option of this field.
+    fn dump_converted(&self, value: &Value) -> Vec<String> {
+        let mut map = Map::with_capacity(1);
+        map.insert(self.label.clone(), Value::String(self.revert(value)));
+        ::value2array(&Value::Object(map))
+    }
+    /// Dumps converted items of `value` to CLI as option of this field.
+    fn dump_converted_items(&self, value: &Value) -> Vec<String> {
+        let items = value
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .map(|x| Value::String(self.revert(x)))
+                    .collect::<Vec<Value>>()
+            })
+            .unwrap_or_default();
+        let mut map = Map::with_capacity(1);
+        map.insert(self.label.clone(), Value::Array(items));
+        ::value2array(&Value::Object(map))
     }
 }
 
 /// Container for field's errors
-pub type FieldErrors = Vec<String>;
+pub type FieldErrors = Vec<ValidationError>;
 
 /// Covers communication from `Form` to `Field`.
 pub trait FormField {
@@ -117,6 +298,34 @@ pub trait FormField {
     }
     /// Validates `data`.
     fn validate(&self, data: &str) -> Result<Value, FieldErrors>;
+    /// Converts field's `value` (result of `validate`) back to `data` accepted by `validate`.
+    ///
+    /// It's used to edit already validated values (like records of [SubForm]).
+    ///
+    /// [SubForm]: struct.SubForm.html
+    fn value2str(&self, value: &Value) -> String {
+        match *value {
+            Value::Null => "".to_string(),
+            Value::String(ref s) => s.clone(),
+            ref other => other.to_string(),
+        }
+    }
+    /// Validates `data` knowing `values` of other fields (see [Field::context_validator]).
+    ///
+    /// [Field::context_validator]: struct.Field.html#method.context_validator
+    fn validate_in_context(
+        &self,
+        _data: &str,
+        _values: &Map<String, Value>,
+    ) -> Result<(), FieldErrors> {
+        Ok(())
+    }
+    /// Checks valid `data` returning warnings (see [Warn]), which don't make it invalid.
+    ///
+    /// [Warn]: ../validators/struct.Warn.html
+    fn warnings(&self, _data: &str, _values: &Map<String, Value>) -> FieldErrors {
+        Vec::new()
+    }
     /// Gets `field`'s label.
     fn get_label(&self) -> &str;
     /// Gets `field`'s help
@@ -130,12 +339,35 @@ pub trait FormField {
     /// [clap::Arg]: ../../clap/struct.Arg.html
     /// [clap::App]: ../../clap/struct.App.html
     fn clap_arg(&self) -> clap::Arg;
+    /// Builds all [clap::Arg]s needed by this field.
+    ///
+    /// Most of fields need a single argument, see [clap_arg].
+    ///
+    /// [clap::Arg]: ../../clap/struct.Arg.html
+    /// [clap_arg]: trait.FormField.html#tymethod.clap_arg
+    fn clap_args(&self) -> Vec<clap::Arg<'_, '_>> {
+        vec![self.clap_arg()]
+    }
     /// Extracts field's data from [clap::ArgMatches] and converts it to str.
     ///
     /// [clap::App]: ../../clap/struct.ArgMatches.html
     fn clap_args2str(&self, args: &clap::ArgMatches) -> String;
+    /// Converts field's `value` (result of `validate`) to CLI arguments.
+    fn dump_as_cli(&self, value: &Value) -> Vec<String> {
+        let mut map = Map::with_capacity(1);
+        map.insert(self.get_label().to_string(), value.clone());
+        ::value2array(&Value::Object(map))
+    }
     /// Checks if Field is required
     fn is_required(&self) -> bool;
+    /// Finds problems in definition of this field (see [FormView::check]).
+    ///
+    /// By default it checks if (non-empty) initial value is valid.
+    ///
+    /// [FormView::check]: ../form/struct.FormView.html#method.check
+    fn check(&self) -> Vec<Diagnostic> {
+        check_initial(self)
+    }
     /// Sets `error` on widget.
     fn set_error(&self, viewbox: &mut views::ViewBox, error: &str) {
         let layout: &mut views::LinearLayout = (**viewbox).as_any_mut().downcast_mut().unwrap();
@@ -143,6 +375,44 @@ pub trait FormField {
         let text: &mut views::TextView = (*child).as_any_mut().downcast_mut().unwrap();
         text.set_content(error);
     }
+    /// Sets `warning` on widget (in place of error, but styled differently).
+    fn set_warning(&self, viewbox: &mut views::ViewBox, warning: &str) {
+        let layout: &mut views::LinearLayout = (**viewbox).as_any_mut().downcast_mut().unwrap();
+        let child: &mut dyn View = (*layout).get_child_mut(2).unwrap();
+        let text: &mut views::TextView = (*child).as_any_mut().downcast_mut().unwrap();
+        let color = Color::Dark(BaseColor::Yellow);
+        text.set_content(StyledString::styled(warning, color));
+    }
+    /// Checks if widget depends on values of other fields (like suggestions of
+    /// [ContextFeeder]).
+    ///
+    /// [ContextFeeder]: ../feeders/trait.ContextFeeder.html
+    fn uses_context(&self) -> bool {
+        false
+    }
+    /// Updates widget with valid `values` of other fields, called by form
+    /// when they change.
+    fn set_context(&self, _viewbox: &mut views::ViewBox, _values: &Map<String, Value>) {}
+}
+
+/// Checks if initial value of `field` (if not empty) passes its validation.
+pub(crate) fn check_initial<F: FormField + ?Sized>(field: &F) -> Vec<Diagnostic> {
+    let initial = field.get_initial();
+    if initial.is_empty() {
+        return Vec::new();
+    }
+    match field.validate(&initial) {
+        Ok(_) => Vec::new(),
+        Err(errors) => errors
+            .into_iter()
+            .map(|e| {
+                let msg = Message::new("check.invalid_initial")
+                    .param("value", format!("{:?}", initial))
+                    .param("error", e);
+                Diagnostic::new(msg)
+            })
+            .collect(),
+    }
 }
 
 fn format_annotation(label: &str, help: &str) -> String {
@@ -171,3 +441,12 @@ pub fn value_view_from_layout(layout: &views::ViewBox) -> &views::ViewBox {
     let value_view: &View = layout.get_child(1).unwrap();
     (*value_view).as_any().downcast_ref().unwrap()
 }
+
+/// Finds view storing value in widget layout
+///
+/// Mutable variant.
+pub fn value_view_from_layout_mut(layout: &mut views::ViewBox) -> &mut views::ViewBox {
+    let layout: &mut views::LinearLayout = (**layout).as_any_mut().downcast_mut().unwrap();
+    let value_view: &mut dyn View = layout.get_child_mut(1).unwrap();
+    (*value_view).as_any_mut().downcast_mut().unwrap()
+}
@@ -3,9 +3,10 @@ use std::rc::Rc;
 
 use clap;
 use cursive::views::ViewBox;
+use serde_json::map::Map;
 use serde_json::value::Value;
 
-use feeders::{DummyFeeder, Feeder};
+use feeders::{ContextFeeder, Contextual, DummyFeeder, Feeder};
 use fields;
 use fields::{label_with_help_layout, Field, FieldErrors, FormField, WidgetManager};
 use views;
@@ -24,12 +25,23 @@ impl Multiselect {
         let mngr = MultiselectManager::with_feeder(feeder);
         Field::new(label, mngr, Vec::new())
     }
+
+    /// Creates a new `Field<MultiselectManager, Vec<String>>` with suggestions
+    /// depending on values of other fields.
+    pub fn with_context<IS: Into<String>, F: ContextFeeder>(
+        label: IS,
+        feeder: F,
+    ) -> Field<MultiselectManager, Vec<String>> {
+        let mngr = MultiselectManager::with_context_feeder(feeder);
+        Field::new(label, mngr, Vec::new())
+    }
 }
 
 #[derive(Clone)]
 pub struct MultiselectManager {
     feeder: Rc<Feeder>,
     view_factory: Option<Rc<Fn() -> views::Multiselect>>,
+    context: Option<Contextual>,
 }
 
 impl MultiselectManager {
@@ -45,6 +57,19 @@ impl MultiselectManager {
         MultiselectManager {
             feeder: Rc::new(feeder),
             view_factory: None,
+            context: None,
+        }
+    }
+
+    /// Creates an instance with a [ContextFeeder] (which gets values of other fields).
+    ///
+    /// [ContextFeeder]: ../../feeders/trait.ContextFeeder.html
+    pub fn with_context_feeder<T: ContextFeeder>(feeder: T) -> Self {
+        let context = Contextual::new(feeder);
+        MultiselectManager {
+            feeder: Rc::new(context.clone()),
+            view_factory: None,
+            context: Some(context),
         }
     }
     /// Creates an instance with customized [views::Multiselect].
@@ -60,6 +85,7 @@ impl MultiselectManager {
             // it should be an option of Rc :)
             feeder: Rc::new(DummyFeeder),
             view_factory: Some(factory),
+            context: None,
         }
     }
 
@@ -108,25 +134,31 @@ impl FormField for Field<MultiselectManager, Vec<String>> {
     }
     fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
         let mut errors = FieldErrors::new();
-        let items = data.split(VALUE_SEP).collect::<Vec<&str>>();
-        for item in items.iter() {
-            for v in &self.validators {
-                if let Some(e) = v.validate(item) {
-                    errors.push(e);
-                }
+        let mut values = Vec::new();
+        for item in data.split(VALUE_SEP) {
+            match self.convert_and_validate(item) {
+                Ok(v) => values.push(v),
+                Err(mut e) => errors.append(&mut e),
             }
         }
         if errors.len() > 0 {
             Err(errors)
         } else {
-            let vec_str = items
-                .iter()
-                .map(|x| Value::String(x.to_string()))
-                .collect::<Vec<Value>>();
-            let val_of_vec = Value::Array(vec_str);
-            Ok(val_of_vec)
+            Ok(Value::Array(values))
         }
     }
+
+    validate_in_context!();
+
+    fn warnings(&self, data: &str, values: &Map<String, Value>) -> FieldErrors {
+        let mut warnings = FieldErrors::new();
+        for item in data.split(VALUE_SEP) {
+            warnings.extend(self.item_warnings(item));
+        }
+        warnings.extend(self.context_warnings(data, values));
+        warnings
+    }
+
     fn get_label(&self) -> &str {
         &self.label
     }
@@ -155,9 +187,29 @@ impl FormField for Field<MultiselectManager, Vec<String>> {
             .unwrap_or(clap::Values::default());
         values.collect::<Vec<&str>>().join(VALUE_SEP)
     }
+    fn dump_as_cli(&self, value: &Value) -> Vec<String> {
+        self.dump_converted_items(value)
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        self.revert_items(value, VALUE_SEP)
+    }
     fn is_required(&self) -> bool {
         self.is_required()
     }
+
+    fn uses_context(&self) -> bool {
+        self.widget_manager.context.is_some()
+    }
+
+    fn set_context(&self, viewbox: &mut ViewBox, values: &Map<String, Value>) {
+        if let Some(ref context) = self.widget_manager.context {
+            context.set_values(values.clone());
+            let view_box = fields::value_view_from_layout_mut(viewbox);
+            let ms: &mut views::Multiselect = (**view_box).as_any_mut().downcast_mut().unwrap();
+            ms.refresh();
+        }
+    }
 }
 
 impl<W: WidgetManager> Field<W, Vec<String>> {
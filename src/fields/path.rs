@@ -0,0 +1,413 @@
+use std::path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use clap;
+use cursive::traits::{Boxable, Identifiable, View};
+use cursive::view::ViewWrapper;
+use cursive::views::{BoxView, Button, Dialog, IdView, LinearLayout, ViewBox};
+use cursive::Cursive;
+use serde_json::map::Map;
+use serde_json::value::Value;
+
+use feeders::DirItems;
+use fields;
+use fields::{Field, FieldErrors, FormField, WidgetManager};
+use messages;
+use views;
+
+const PATHS_SEP: &str = "\n";
+const BROWSER_ID: &str = "fui-path-browser";
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Convienient wrapper around `Field<PathManager, String>`
+/// (or `Field<PathManager, Vec<String>>`).
+///
+/// Path can be typed (with suggestions) or picked in a dir tree browser
+/// (opened by button `Browse`).
+pub struct Path;
+
+impl Path {
+    /// Creates a new `Field<PathManager, String>`.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<IS: Into<String>>(label: IS) -> Field<PathManager, String> {
+        Field::new(label, PathManager::new(false), "".to_string())
+    }
+
+    /// Creates a new `Field<PathManager, Vec<String>>` which allows many paths.
+    pub fn multiple<IS: Into<String>>(label: IS) -> Field<PathManager, Vec<String>> {
+        Field::new(label, PathManager::new(true), Vec::new())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct BrowserOptions {
+    root: Option<String>,
+    show_hidden: bool,
+    dirs_only: bool,
+    extensions: Vec<String>,
+}
+
+#[derive(Clone)]
+pub struct PathManager {
+    id: String,
+    multiple: bool,
+    options: BrowserOptions,
+}
+
+impl PathManager {
+    fn new(multiple: bool) -> Self {
+        PathManager {
+            id: format!("fui-path-{}", NEXT_ID.fetch_add(1, Ordering::SeqCst)),
+            multiple,
+            options: BrowserOptions {
+                root: None,
+                show_hidden: false,
+                dirs_only: false,
+                extensions: Vec::new(),
+            },
+        }
+    }
+
+    fn dir_items(&self) -> DirItems {
+        if self.options.dirs_only {
+            DirItems::dirs()
+        } else {
+            DirItems::new()
+        }
+    }
+}
+
+/// Finds dir where browsing starts, it's a dir of `current` path if it exists.
+fn browser_root(options: &BrowserOptions, current: Option<String>) -> String {
+    if let Some(current) = current {
+        let current = path::Path::new(&current);
+        if current.is_dir() {
+            return format!("{}", current.display());
+        }
+        if let Some(parent) = current.parent() {
+            if parent.is_dir() && !parent.as_os_str().is_empty() {
+                return format!("{}", parent.display());
+            }
+        }
+    }
+    options.root.clone().unwrap_or(".".to_string())
+}
+
+/// Shows dir tree browser which puts selected paths to view with `target` id.
+fn open_browser(c: &mut Cursive, target: &str, options: &BrowserOptions, multiple: bool) {
+    let current = if multiple {
+        None
+    } else {
+        c.call_on_id(target, |ac: &mut views::Autocomplete| {
+            (*ac.get_value()).clone()
+        })
+    };
+    let mut browser = views::PathBrowser::new(browser_root(options, current))
+        .show_hidden(options.show_hidden)
+        .extensions(options.extensions.clone());
+    if options.dirs_only {
+        browser = browser.dirs_only();
+    }
+    if multiple {
+        browser = browser.multiple();
+    }
+    let submit_target = target.to_string();
+    let browser = browser.on_submit(move |c, paths| {
+        set_paths(c, &submit_target, paths, multiple);
+    });
+    let button_target = target.to_string();
+    let dialog = Dialog::around(browser.with_id(BROWSER_ID))
+        .title(messages::get("path.browse"))
+        .button(messages::get("dialog.cancel"), |c| {
+            c.pop_layer();
+        })
+        .button(messages::get("path.select"), move |c| {
+            let paths = c
+                .call_on_id(BROWSER_ID, |b: &mut views::PathBrowser| b.get_selection())
+                .unwrap_or(Vec::new());
+            set_paths(c, &button_target, paths, multiple);
+        })
+        .full_screen();
+    c.add_layer(dialog);
+}
+
+fn set_paths(c: &mut Cursive, target: &str, paths: Vec<String>, multiple: bool) {
+    c.pop_layer();
+    if multiple {
+        c.call_on_id(target, |ms: &mut views::Multiselect| ms.select_items(paths));
+    } else if let Some(path) = paths.into_iter().next() {
+        c.call_on_id(target, |ac: &mut views::Autocomplete| ac.set_value(&path));
+    }
+}
+
+impl WidgetManager for PathManager {
+    fn build_value_view(&self, value: &str) -> ViewBox {
+        let target = self.id.clone();
+        let options = self.options.clone();
+        let multiple = self.multiple;
+        let browse = Button::new(messages::get("path.browse"), move |c| {
+            open_browser(c, &target, &options, multiple)
+        });
+        let layout = if self.multiple {
+            let mut ms = views::Multiselect::new(self.dir_items())
+                .select_anything()
+                .redundant_selection();
+            let items = value
+                .split(PATHS_SEP)
+                .filter(|x| !x.is_empty())
+                .map(|x| x.to_string())
+                .collect::<Vec<String>>();
+            ms.select_items(items);
+            LinearLayout::horizontal()
+                .child(ms.with_id(self.id.as_str()).full_width())
+                .child(browse)
+        } else {
+            let ac = views::Autocomplete::new(self.dir_items())
+                .submit_anything()
+                .value(value);
+            LinearLayout::horizontal()
+                .child(ac.with_id(self.id.as_str()).full_width())
+                .child(browse)
+        };
+        ViewBox::new(Box::new(layout))
+    }
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> ViewBox {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &ViewBox) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let layout: &LinearLayout = (**view_box).as_any().downcast_ref().unwrap();
+        let child: &dyn View = layout.get_child(0).unwrap();
+        if self.multiple {
+            let ms: &BoxView<IdView<views::Multiselect>> = child.as_any().downcast_ref().unwrap();
+            ms.get_inner()
+                .with_view(|ms| {
+                    ms.get_selected_items()
+                        .iter()
+                        .map(|x| (*x).to_owned())
+                        .collect::<Vec<String>>()
+                        .join(PATHS_SEP)
+                })
+                .unwrap_or("".to_string())
+        } else {
+            let ac: &BoxView<IdView<views::Autocomplete>> = child.as_any().downcast_ref().unwrap();
+            ac.get_inner()
+                .with_view(|ac| (*ac.get_value()).clone())
+                .unwrap_or("".to_string())
+        }
+    }
+}
+
+impl<T> Field<PathManager, T> {
+    /// Sets dir where browsing starts (current dir by default).
+    pub fn root<IS: Into<String>>(mut self, root: IS) -> Self {
+        self.widget_manager.options.root = Some(root.into());
+        self
+    }
+
+    /// Shows hidden files in browser.
+    pub fn show_hidden(mut self) -> Self {
+        self.widget_manager.options.show_hidden = true;
+        self
+    }
+
+    /// Suggests only dirs.
+    pub fn dirs_only(mut self) -> Self {
+        self.widget_manager.options.dirs_only = true;
+        self
+    }
+
+    /// Shows only files with given `extensions` in browser.
+    pub fn extensions<S: Into<String>>(mut self, extensions: Vec<S>) -> Self {
+        self.widget_manager.options.extensions = extensions.into_iter().map(|x| x.into()).collect();
+        self
+    }
+}
+
+impl FormField for Field<PathManager, String> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        self.convert_and_validate(data)
+    }
+
+    validate_in_context!();
+
+    fn warnings(&self, data: &str, values: &Map<String, Value>) -> FieldErrors {
+        self.run_warnings(data, values)
+    }
+
+    /// Gets label of the field
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.clone()
+    }
+
+    fn clap_arg(&self) -> clap::Arg<'_, '_> {
+        clap::Arg::with_name(&self.label)
+            .long(&self.label)
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        args.value_of(&self.label).unwrap_or("").to_string()
+    }
+
+    fn dump_as_cli(&self, value: &Value) -> Vec<String> {
+        self.dump_converted(value)
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        self.revert(value)
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+}
+
+impl FormField for Field<PathManager, Vec<String>> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        let items = data
+            .split(PATHS_SEP)
+            .filter(|x| !x.is_empty())
+            .collect::<Vec<&str>>();
+        if items.is_empty() {
+            errors.extend(self.run_validators("").0);
+        }
+        let mut paths = Vec::with_capacity(items.len());
+        for item in items.iter() {
+            match self.convert_and_validate(item) {
+                Ok(v) => paths.push(v),
+                Err(e) => errors.extend(e.into_iter().map(|mut e| {
+                    e.message = format!("{}: {}", item, e.message);
+                    e
+                })),
+            }
+        }
+        if !errors.is_empty() {
+            Err(errors)
+        } else {
+            Ok(Value::Array(paths))
+        }
+    }
+
+    validate_in_context!();
+
+    fn warnings(&self, data: &str, values: &Map<String, Value>) -> FieldErrors {
+        let mut warnings = FieldErrors::new();
+        for item in data.split(PATHS_SEP).filter(|x| !x.is_empty()) {
+            warnings.extend(self.item_warnings(item).into_iter().map(|mut e| {
+                e.message = format!("{}: {}", item, e.message);
+                e
+            }));
+        }
+        warnings.extend(self.context_warnings(data, values));
+        warnings
+    }
+
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.join(PATHS_SEP)
+    }
+
+    fn clap_arg(&self) -> clap::Arg<'_, '_> {
+        clap::Arg::with_name(&self.label)
+            .long(&self.label)
+            .help(&self.help)
+            .required(self.is_required())
+            .multiple(true)
+            .takes_value(true)
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        let values = args.values_of(&self.label).unwrap_or_default();
+        values.collect::<Vec<&str>>().join(PATHS_SEP)
+    }
+
+    fn dump_as_cli(&self, value: &Value) -> Vec<String> {
+        self.dump_converted_items(value)
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        self.revert_items(value, PATHS_SEP)
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use validators::{FileExists, Required};
+
+    #[test]
+    fn path_value_is_taken_from_widget() {
+        let field = Path::new("file").initial("./Cargo.toml");
+        let widget = field.build_widget();
+        let value = field.get_widget_manager().get_value(&widget);
+        assert_eq!(value, "./Cargo.toml");
+    }
+
+    #[test]
+    fn multiple_paths_are_taken_from_widget() {
+        let field = Path::multiple("files").initial(vec!["./Cargo.toml", "./src"]);
+        let widget = field.build_widget();
+        let value = field.get_widget_manager().get_value(&widget);
+        assert_eq!(field.validate(&value), Ok(json!(["./Cargo.toml", "./src"])));
+    }
+
+    #[test]
+    fn multiple_paths_are_validated_one_by_one() {
+        let field = Path::multiple("files").validator(FileExists);
+        assert_eq!(
+            field.validate("./Cargo.toml\n./src").unwrap_err(),
+            vec!["./src: It's not a file".to_string()]
+        );
+    }
+
+    #[test]
+    fn multiple_paths_can_be_required() {
+        let field = Path::multiple("files").validator(Required);
+        assert_eq!(
+            field.validate("").unwrap_err(),
+            vec!["Field is required".to_string()]
+        );
+    }
+
+    #[test]
+    fn browser_starts_in_dir_of_current_path() {
+        let options = PathManager::new(false).options;
+        assert_eq!(browser_root(&options, Some("./src/lib.rs".into())), "./src");
+        assert_eq!(browser_root(&options, Some("./src".into())), "./src");
+        assert_eq!(browser_root(&options, Some("missing".into())), ".");
+        assert_eq!(browser_root(&options, None), ".");
+    }
+}
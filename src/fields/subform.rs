@@ -0,0 +1,727 @@
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use clap;
+use cursive::event::{Event, EventResult, Key};
+use cursive::traits::View;
+use cursive::view::ViewWrapper;
+use cursive::views::{Button, LinearLayout, Panel, SelectView, ViewBox};
+use serde_json;
+use serde_json::map::Map;
+use serde_json::value::Value;
+
+use fields;
+use fields::{Field, FieldErrors, FormField, WidgetManager};
+use form;
+use form::{Diagnostic, FormErrors};
+use messages;
+use messages::Message;
+
+const EDITOR_IDX: usize = 0;
+const ADD_IDX: usize = 1;
+const RECORDS_IDX: usize = 2;
+
+/// Errors of sub-form's records, grouped by record's index.
+///
+/// Sub-form which is not repeatable has only one record (with index `0`).
+pub type RecordErrors = BTreeMap<usize, FormErrors>;
+
+/// Convienient wrapper around `Field<SubFormManager, Value>`.
+///
+/// `SubForm` embeds other fields, its value is a JSON object
+/// (or an array of JSON objects if it's repeatable).
+///
+/// Initial value is a JSON object (or an array of objects) of fields' raw values,
+/// like: `{"host": "80", "proto": "tcp"}`.
+pub struct SubForm;
+
+impl SubForm {
+    /// Creates a new `Field<SubFormManager, Value>` holding a single record.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<IS: Into<String>>(label: IS) -> Field<SubFormManager, Value> {
+        let mngr = SubFormManager {
+            fields: Vec::new(),
+            repeatable: false,
+        };
+        Field::new(label, mngr, Value::Object(Map::new()))
+    }
+
+    /// Creates a new `Field<SubFormManager, Value>` holding many records.
+    pub fn repeatable<IS: Into<String>>(label: IS) -> Field<SubFormManager, Value> {
+        let mngr = SubFormManager {
+            fields: Vec::new(),
+            repeatable: true,
+        };
+        Field::new(label, mngr, Value::Array(Vec::new()))
+    }
+}
+
+#[derive(Clone)]
+pub struct SubFormManager {
+    fields: Vec<Rc<dyn FormField>>,
+    repeatable: bool,
+}
+
+/// Gets data of `field` from `record` (or its initial value).
+///
+/// Texts are data from editor, other values (like from CLI) are converted back
+/// by `field`.
+fn record_data(field: &dyn FormField, record: &Map<String, Value>) -> String {
+    match record.get(field.get_label()) {
+        Some(Value::String(s)) => s.to_owned(),
+        Some(value) => field.value2str(value),
+        None => field.get_initial(),
+    }
+}
+
+/// Builds widgets of `fields` with values taken from `record` (or initial ones).
+fn build_editor(fields: &[Rc<dyn FormField>], record: Option<&Map<String, Value>>) -> LinearLayout {
+    let mut layout = LinearLayout::vertical();
+    for field in fields.iter() {
+        let data = match record {
+            Some(record) => record_data(&**field, record),
+            None => field.get_initial(),
+        };
+        let view = field.get_widget_manager().build_value_view(&data);
+        layout.add_child(fields::label_with_help_layout(
+            view,
+            field.get_label(),
+            field.get_help(),
+        ));
+    }
+    layout
+}
+
+/// Collects raw values of `fields` from `editor`.
+fn editor2record(fields: &[Rc<dyn FormField>], editor: &LinearLayout) -> Map<String, Value> {
+    let mut record = Map::with_capacity(fields.len());
+    for (idx, field) in fields.iter().enumerate() {
+        let child = editor.get_child(idx).unwrap();
+        let view_box: &ViewBox = child.as_any().downcast_ref().unwrap();
+        let raw = field.get_widget_manager().get_value(view_box);
+        record.insert(field.get_label().to_string(), Value::String(raw));
+    }
+    record
+}
+
+fn record_summary(fields: &[Rc<dyn FormField>], record: &Map<String, Value>) -> String {
+    fields
+        .iter()
+        .filter(|field| record.contains_key(field.get_label()))
+        .map(|field| format!("{}={}", field.get_label(), record_data(&**field, record)))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Editor of a single record with a list of already added records.
+///
+/// * `Enter` on button `Add record` moves edited record to the list
+/// * `Enter` on listed record moves it back to edition
+/// * `Delete` on listed record removes it
+/// * record left in editor is submitted as the last one
+struct RecordsView {
+    view: LinearLayout,
+    fields: Vec<Rc<dyn FormField>>,
+}
+
+impl RecordsView {
+    fn new(fields: Vec<Rc<dyn FormField>>, records: Vec<Map<String, Value>>) -> Self {
+        let mut select = SelectView::<String>::new();
+        for record in records.iter() {
+            select.add_item(
+                record_summary(&fields, record),
+                Value::Object(record.clone()).to_string(),
+            );
+        }
+        let layout = LinearLayout::vertical()
+            .child(Panel::new(build_editor(&fields, None)))
+            .child(Button::new(messages::get("subform.add_record"), |_| {}))
+            .child(Panel::new(select));
+        RecordsView {
+            view: layout,
+            fields,
+        }
+    }
+
+    fn get_editor(&self) -> &LinearLayout {
+        self.view
+            .get_child(EDITOR_IDX)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Panel<LinearLayout>>()
+            .unwrap()
+            .get_inner()
+    }
+
+    fn set_editor(&mut self, record: Option<&Map<String, Value>>) {
+        let editor = build_editor(&self.fields, record);
+        *self
+            .view
+            .get_child_mut(EDITOR_IDX)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<Panel<LinearLayout>>()
+            .unwrap()
+            .get_inner_mut() = editor;
+    }
+
+    fn get_records_view(&self) -> &SelectView<String> {
+        self.view
+            .get_child(RECORDS_IDX)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Panel<SelectView<String>>>()
+            .unwrap()
+            .get_inner()
+    }
+
+    fn get_records_view_mut(&mut self) -> &mut SelectView<String> {
+        self.view
+            .get_child_mut(RECORDS_IDX)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<Panel<SelectView<String>>>()
+            .unwrap()
+            .get_inner_mut()
+    }
+
+    /// Returns records as JSON texts.
+    ///
+    /// Record filled in editor (but not added yet) is the last one, so it's
+    /// validated instead of being dropped.
+    fn get_records(&self) -> Vec<String> {
+        let mut records = self
+            .get_records_view()
+            .iter()
+            .map(|(_, record)| record.clone())
+            .collect::<Vec<String>>();
+        if let Some(record) = self.get_pending() {
+            records.push(Value::Object(record).to_string());
+        }
+        records
+    }
+
+    /// Returns edited record if it differs from a new one.
+    fn get_pending(&self) -> Option<Map<String, Value>> {
+        let record = editor2record(&self.fields, self.get_editor());
+        let untouched = self
+            .fields
+            .iter()
+            .all(|field| record_data(&**field, &record) == field.get_initial());
+        if untouched {
+            None
+        } else {
+            Some(record)
+        }
+    }
+
+    fn add_edited(&mut self) {
+        let record = editor2record(&self.fields, self.get_editor());
+        let summary = record_summary(&self.fields, &record);
+        self.get_records_view_mut()
+            .add_item(summary, Value::Object(record).to_string());
+        self.set_editor(None);
+    }
+
+    fn edit_selected(&mut self) {
+        if let Some(record) = self.remove_selected() {
+            let record: Value = serde_json::from_str(&record).unwrap_or(json!({}));
+            self.set_editor(record.as_object());
+            // at most: records -> button -> editor
+            for _ in 0..2 {
+                if self.view.get_focus_index() == EDITOR_IDX {
+                    break;
+                }
+                let tab_event = Event::Shift(Key::Tab);
+                self.with_view_mut(|v| v.on_event(tab_event));
+            }
+        }
+    }
+
+    fn remove_selected(&mut self) -> Option<String> {
+        let select = self.get_records_view_mut();
+        if let Some(idx) = select.selected_id() {
+            let record = select.selection().map(|x| (*x).clone());
+            select.remove_item(idx);
+            record
+        } else {
+            None
+        }
+    }
+}
+
+impl ViewWrapper for RecordsView {
+    wrap_impl!(self.view: LinearLayout);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        let focused = self.view.get_focus_index();
+        match event {
+            Event::Key(Key::Enter) if focused == ADD_IDX => {
+                self.add_edited();
+                EventResult::Consumed(None)
+            }
+            Event::Key(Key::Enter) if focused == RECORDS_IDX => {
+                self.edit_selected();
+                EventResult::Consumed(None)
+            }
+            Event::Key(Key::Del) if focused == RECORDS_IDX => {
+                self.remove_selected();
+                EventResult::Consumed(None)
+            }
+            _ => self
+                .with_view_mut(|v| v.on_event(event))
+                .unwrap_or(EventResult::Ignored),
+        }
+    }
+}
+
+impl WidgetManager for SubFormManager {
+    fn build_value_view(&self, initial: &str) -> ViewBox {
+        let initial: Value = serde_json::from_str(initial).unwrap_or(Value::Null);
+        if self.repeatable {
+            let records = initial
+                .as_array()
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|x| x.as_object().cloned())
+                        .collect()
+                })
+                .unwrap_or_default();
+            ViewBox::new(Box::new(RecordsView::new(self.fields.clone(), records)))
+        } else {
+            let editor = build_editor(&self.fields, initial.as_object());
+            ViewBox::new(Box::new(Panel::new(editor)))
+        }
+    }
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> ViewBox {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &ViewBox) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        if self.repeatable {
+            let view: &RecordsView = (**view_box).as_any().downcast_ref().unwrap();
+            let records = view.get_records();
+            if !records.is_empty() {
+                format!("[{}]", records.join(","))
+            } else {
+                "".to_string()
+            }
+        } else {
+            let panel: &Panel<LinearLayout> = (**view_box).as_any().downcast_ref().unwrap();
+            Value::Object(editor2record(&self.fields, panel.get_inner())).to_string()
+        }
+    }
+}
+
+impl Field<SubFormManager, Value> {
+    /// Appends `field` to sub-form's fields.
+    pub fn field<V: FormField + 'static>(mut self, field: V) -> Self {
+        self.widget_manager.fields.push(Rc::new(field));
+        self
+    }
+
+    /// Validates `record` against sub-form's fields (like form validates its fields),
+    /// returning its value with warnings or errors.
+    fn validate_record(
+        &self,
+        record: &Map<String, Value>,
+    ) -> Result<(Value, FormErrors), FormErrors> {
+        let fields = &self.widget_manager.fields;
+        let data = fields
+            .iter()
+            .map(|field| record_data(&**field, record))
+            .collect::<Vec<String>>();
+        form::validate_fields(fields.iter().map(|f| &**f), &data)
+    }
+
+    /// Validates `data` returning errors grouped by record and field.
+    pub fn validate_records(&self, data: &str) -> Result<Value, RecordErrors> {
+        self.check_records(data).map(|(value, _)| value)
+    }
+
+    /// Validates `data` returning its value with warnings (or errors) grouped
+    /// by record and field.
+    fn check_records(&self, data: &str) -> Result<(Value, RecordErrors), RecordErrors> {
+        let parsed: Value = if data.trim().is_empty() {
+            if self.widget_manager.repeatable {
+                Value::Array(Vec::new())
+            } else {
+                Value::Object(Map::new())
+            }
+        } else {
+            serde_json::from_str(data).map_err(|e| {
+                let mut form_errors = FormErrors::new();
+                let msg = Message::new("subform.invalid_json").param("error", e);
+                form_errors.insert(self.label.clone(), vec![msg.into()]);
+                let mut errors = RecordErrors::new();
+                errors.insert(0, form_errors);
+                errors
+            })?
+        };
+        let records = match (self.widget_manager.repeatable, parsed) {
+            (true, Value::Array(items)) => items,
+            (false, record @ Value::Object(_)) => vec![record],
+            (_, _) => {
+                let key = if self.widget_manager.repeatable {
+                    "subform.not_array"
+                } else {
+                    "subform.not_object"
+                };
+                let mut form_errors = FormErrors::new();
+                form_errors.insert(self.label.clone(), vec![Message::new(key).into()]);
+                let mut errors = RecordErrors::new();
+                errors.insert(0, form_errors);
+                return Err(errors);
+            }
+        };
+        let mut values = Vec::with_capacity(records.len());
+        let mut errors = RecordErrors::new();
+        let mut warnings = RecordErrors::new();
+        for (idx, record) in records.iter().enumerate() {
+            let record = record.as_object().cloned().unwrap_or(Map::new());
+            match self.validate_record(&record) {
+                Ok((v, w)) => {
+                    values.push(v);
+                    if !w.is_empty() {
+                        warnings.insert(idx, w);
+                    }
+                }
+                Err(e) => {
+                    errors.insert(idx, e);
+                }
+            }
+        }
+        if !errors.is_empty() {
+            Err(errors)
+        } else if self.widget_manager.repeatable {
+            Ok((Value::Array(values), warnings))
+        } else {
+            Ok((values.pop().unwrap(), warnings))
+        }
+    }
+
+    /// Nests `errors` in sub-form's value, they keep their codes & params
+    /// and get path like `["0", "host"]` and message like `#1 host: Field is required`.
+    fn nest_errors(&self, errors: RecordErrors) -> FieldErrors {
+        let mut nested = FieldErrors::new();
+        for (idx, form_errors) in errors.into_iter() {
+            // keep fields' order
+            for field in self.widget_manager.fields.iter() {
+                let label = field.get_label();
+                if let Some(field_errors) = form_errors.get(label) {
+                    for e in field_errors {
+                        let mut e = e.clone().nested_in(label);
+                        if self.widget_manager.repeatable {
+                            e = e.nested_in(idx.to_string());
+                        }
+                        e.message = self.format_error(idx, label, &e.message);
+                        nested.push(e);
+                    }
+                }
+            }
+            if let Some(field_errors) = form_errors.get(&self.label) {
+                nested.extend(field_errors.iter().cloned());
+            }
+        }
+        nested
+    }
+
+    fn format_error(&self, idx: usize, label: &str, error: &str) -> String {
+        let msg = if self.widget_manager.repeatable {
+            Message::new("subform.record_error").param("record", idx + 1)
+        } else {
+            Message::new("subform.field_error")
+        };
+        msg.param("label", label).param("error", error).to_string()
+    }
+}
+
+impl FormField for Field<SubFormManager, Value> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let (errors, _) = self.run_validators(data);
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        let value = self
+            .validate_records(data)
+            .map_err(|e| self.nest_errors(e))?;
+        self.convert_value(value).map_err(|e| vec![e])
+    }
+
+    validate_in_context!();
+
+    fn warnings(&self, data: &str, values: &Map<String, Value>) -> FieldErrors {
+        let mut warnings = self.run_warnings(data, values);
+        if let Ok((_, nested)) = self.check_records(data) {
+            warnings.extend(self.nest_errors(nested));
+        }
+        warnings
+    }
+
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        self.initial.to_string()
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        self.revert(value)
+    }
+
+    fn clap_arg(&self) -> clap::Arg<'_, '_> {
+        let arg = clap::Arg::with_name(&self.label)
+            .long(&self.label)
+            .help(&self.help)
+            .required(self.is_required())
+            .takes_value(true);
+        if self.widget_manager.repeatable {
+            arg.multiple(true).number_of_values(1)
+        } else {
+            arg
+        }
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        if self.widget_manager.repeatable {
+            let values = args
+                .values_of(&self.label)
+                .unwrap_or_default()
+                .collect::<Vec<&str>>();
+            if !values.is_empty() {
+                format!("[{}]", values.join(","))
+            } else {
+                "".to_string()
+            }
+        } else {
+            args.value_of(&self.label).unwrap_or("").to_string()
+        }
+    }
+
+    fn dump_as_cli(&self, value: &Value) -> Vec<String> {
+        let value = self.revert_value(value);
+        let records = match value {
+            Value::Array(ref items) => items.iter().collect(),
+            Value::Object(_) => vec![&value],
+            _ => Vec::new(),
+        };
+        let mut args = Vec::with_capacity(records.len() * 2);
+        for record in records {
+            args.push(format!("--{}", self.label));
+            args.push(record.to_string());
+        }
+        args
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+
+    fn check(&self) -> Vec<Diagnostic> {
+        // empty initial value isn't validated (sub-fields have own initials)
+        let empty = match self.initial {
+            Value::Object(ref map) => map.is_empty(),
+            Value::Array(ref items) => items.is_empty(),
+            _ => false,
+        };
+        let mut diagnostics = if empty {
+            Vec::new()
+        } else {
+            fields::check_initial(self)
+        };
+        let nested = form::check_fields(self.widget_manager.fields.iter().map(|f| &**f));
+        for diagnostic in nested.into_iter() {
+            let label = match diagnostic.field {
+                Some(ref nested_label) => format!("{}.{}", self.label, nested_label),
+                None => self.label.clone(),
+            };
+            diagnostics.push(diagnostic.field(label));
+        }
+        diagnostics
+    }
+}
+
+impl<W: WidgetManager> Field<W, Value> {
+    /// Sets initial `value` of `field`.
+    pub fn initial(mut self, value: Value) -> Self {
+        self.initial = value;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fields::{Checkbox, KeyValue, Multiselect, Text};
+    use validators::{Required, Warn};
+
+    fn port() -> Field<SubFormManager, Value> {
+        SubForm::repeatable("port")
+            .field(Text::new("host").validator(Required))
+            .field(Text::new("container").validator(Required))
+            .field(Checkbox::new("udp"))
+    }
+
+    #[test]
+    fn sub_form_is_validated_to_object() {
+        let field = SubForm::new("db")
+            .field(Text::new("host"))
+            .field(Checkbox::new("ssl"));
+        let exp: Value = serde_json::from_str(r#"{"host": "localhost", "ssl": false}"#).unwrap();
+        assert_eq!(field.validate(r#"{"host": "localhost"}"#), Ok(exp));
+    }
+
+    #[test]
+    fn repeatable_sub_form_is_validated_to_array() {
+        let exp: Value = serde_json::from_str(
+            r#"[{"host": "80", "container": "8080", "udp": false},
+                {"host": "53", "container": "53", "udp": true}]"#,
+        )
+        .unwrap();
+        let found = port().validate(
+            r#"[{"host": "80", "container": 8080}, {"host": "53", "container": "53", "udp": true}]"#,
+        );
+        assert_eq!(found, Ok(exp));
+    }
+
+    #[test]
+    fn repeatable_sub_form_reports_errors_per_record_and_field() {
+        let errors = port()
+            .validate_records(
+                r#"[{"host": "80", "container": "80"}, {"host": "", "container": ""}]"#,
+            )
+            .unwrap_err();
+        assert_eq!(errors.keys().collect::<Vec<&usize>>(), vec![&1]);
+        assert_eq!(errors[&1]["host"], vec!["Field is required"]);
+        assert_eq!(errors[&1]["container"], vec!["Field is required"]);
+    }
+
+    #[test]
+    fn repeatable_sub_form_nests_errors() {
+        let errors = port()
+            .validate(r#"[{"host": "", "container": "1"}]"#)
+            .unwrap_err();
+        assert_eq!(errors, vec!["#1 host: Field is required"]);
+        assert_eq!(errors[0].code, "validators.required");
+        assert_eq!(&*errors[0].path, ["0", "host"]);
+    }
+
+    #[test]
+    fn nested_sub_form_errors_have_full_path() {
+        let field = SubForm::new("service").field(port());
+        let errors = field
+            .validate(r#"{"port": [{"host": "80", "container": "80"}, {"host": "53"}]}"#)
+            .unwrap_err();
+        assert_eq!(errors, vec!["port: #2 container: Field is required"]);
+        assert_eq!(&*errors[0].path, ["port", "1", "container"]);
+    }
+
+    #[test]
+    fn sub_form_reports_invalid_json() {
+        let errors = port().validate(r#"[{"host": "80""#).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "subform.invalid_json");
+    }
+
+    #[test]
+    fn repeatable_sub_form_rejects_object() {
+        let errors = port().validate(r#"{"host": "80"}"#).unwrap_err();
+        assert_eq!(errors, vec!["Value must be an array of JSON objects"]);
+    }
+
+    fn records_view(widget: &mut ViewBox) -> &mut RecordsView {
+        let view_box = fields::value_view_from_layout_mut(widget);
+        (**view_box).as_any_mut().downcast_mut().unwrap()
+    }
+
+    #[test]
+    fn record_not_added_is_validated() {
+        let field = port().initial(json!([{"host": "80", "container": "80"}]));
+        let mut widget = field.build_widget();
+        let pending: Value = json!({"host": "53", "container": ""});
+        records_view(&mut widget).set_editor(pending.as_object());
+
+        let data = field.widget_manager.get_value(&widget);
+        let errors = field.validate(&data).unwrap_err();
+        assert_eq!(errors, vec!["#2 container: Field is required"]);
+
+        let pending: Value = json!({"host": "53", "container": "53"});
+        records_view(&mut widget).set_editor(pending.as_object());
+        let data = field.widget_manager.get_value(&widget);
+        let value = field.validate(&data).unwrap();
+        assert_eq!(value.as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn untouched_editor_is_not_a_record() {
+        let field = port().initial(json!([{"host": "80", "container": "80"}]));
+        let widget = field.build_widget();
+        let data = field.widget_manager.get_value(&widget);
+        assert_eq!(field.validate(&data).unwrap().as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn structured_values_of_records_are_edited_and_validated_again() {
+        let field = SubForm::repeatable("service")
+            .field(Multiselect::new("tags", vec!["a", "b", "c"]))
+            .field(KeyValue::new("env"))
+            .field(KeyValue::new("labels").as_object());
+        let value = json!([{
+            "tags": ["a", "b"],
+            "env": ["A=1", "B=x=y"],
+            "labels": {"tier": "web"},
+        }]);
+        assert_eq!(field.validate(&value.to_string()), Ok(value.clone()));
+
+        let mut widget = field.build_widget();
+        records_view(&mut widget).set_editor(value[0].as_object());
+        let data = field.widget_manager.get_value(&widget);
+        assert_eq!(field.validate(&data), Ok(value));
+    }
+
+    #[test]
+    fn sub_fields_run_context_validators_and_warnings() {
+        let field = SubForm::new("copy")
+            .field(Text::new("source"))
+            .field(Text::new("target").context_validator(
+                |data: &str, values: &Map<String, Value>| {
+                    if values.get("source") == Some(&json!(data)) {
+                        Err("Target must differ from source".to_string())
+                    } else {
+                        Ok(())
+                    }
+                },
+            ))
+            .field(Text::new("comment").validator(Warn(Required)));
+        let errors = field
+            .validate(r#"{"source": "a", "target": "a"}"#)
+            .unwrap_err();
+        assert_eq!(errors, vec!["target: Target must differ from source"]);
+
+        let data = r#"{"source": "a", "target": "b"}"#;
+        assert!(field.validate(data).is_ok());
+        let warnings = field.warnings(data, &Map::new());
+        assert_eq!(warnings, vec!["comment: Field is required"]);
+        assert!(warnings[0].is_warning());
+    }
+
+    #[test]
+    fn repeatable_sub_form_is_dumped_as_repeated_option() {
+        let value: Value = serde_json::from_str(r#"[{"host": "80"}, {"host": "53"}]"#).unwrap();
+        assert_eq!(
+            port().dump_as_cli(&value),
+            vec!["--port", r#"{"host":"80"}"#, "--port", r#"{"host":"53"}"#]
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use clap;
 use cursive::views;
+use serde_json::map::Map;
 use serde_json::value::Value;
 
 use fields;
@@ -39,17 +40,13 @@ impl fields::FormField for fields::Field<TextManager, String> {
         &self.widget_manager
     }
     fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
-        let mut errors = FieldErrors::new();
-        for v in &self.validators {
-            if let Some(e) = v.validate(data) {
-                errors.push(e);
-            }
-        }
-        if errors.len() > 0 {
-            Err(errors)
-        } else {
-            Ok(Value::String(data.to_owned()))
-        }
+        self.convert_and_validate(data)
+    }
+
+    validate_in_context!();
+
+    fn warnings(&self, data: &str, values: &Map<String, Value>) -> FieldErrors {
+        self.run_warnings(data, values)
     }
 
     /// Gets label of the field
@@ -78,6 +75,14 @@ impl fields::FormField for fields::Field<TextManager, String> {
         args.value_of(&self.label).unwrap_or("").to_string()
     }
 
+    fn dump_as_cli(&self, value: &Value) -> Vec<String> {
+        self.dump_converted(value)
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        self.revert(value)
+    }
+
     fn is_required(&self) -> bool {
         self.is_required()
     }
@@ -90,3 +95,108 @@ impl<W: WidgetManager> fields::Field<W, String> {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use converters::{ToInt, ToList, Trim};
+    use fields::FormField;
+    use validators::{OneOf, PathFree, Required, Warn};
+
+    #[test]
+    fn converters_run_before_validators() {
+        let field = Text::new("level")
+            .converter(Trim)
+            .validator(OneOf(vec!["debug", "info"]));
+        assert_eq!(field.validate("  info "), Ok(json!("info")));
+    }
+
+    #[test]
+    fn converter_errors_are_field_errors() {
+        let field = Text::new("count").converter(ToInt);
+        assert_eq!(
+            field.validate("x").unwrap_err(),
+            vec!["Value \"x\" is not an integer".to_string()]
+        );
+        assert_eq!(field.validate(""), Ok(Value::Null));
+    }
+
+    #[test]
+    fn validators_see_converted_value() {
+        let field = Text::new("count")
+            .converter(ToInt)
+            .validator(OneOf(vec!["7"]));
+        assert_eq!(field.validate("007"), Ok(json!(7)));
+        let field = Text::new("count").converter(ToInt).validator(Required);
+        assert_eq!(
+            field.validate("").unwrap_err(),
+            vec!["Field is required".to_string()]
+        );
+    }
+
+    #[test]
+    fn converted_value_is_dumped_as_cli() {
+        let field = Text::new("tags").converter(Trim).converter(ToList(","));
+        let value = field.validate(" a, b ").unwrap();
+        assert_eq!(value, json!(["a", "b"]));
+        assert_eq!(field.dump_as_cli(&value), vec!["--tags", "a,b"]);
+    }
+
+    #[test]
+    fn errors_have_codes() {
+        let field = Text::new("port").converter(ToInt).validator(Required);
+        assert_eq!(
+            field.validate("").unwrap_err()[0].code,
+            "validators.required"
+        );
+        assert_eq!(
+            field.validate("x").unwrap_err()[0].code,
+            "converters.not_integer"
+        );
+    }
+
+    #[test]
+    fn warnings_do_not_make_value_invalid() {
+        let field = Text::new("out").validator(Warn(PathFree));
+        assert_eq!(field.validate("./src"), Ok(json!("./src")));
+        let warnings = field.warnings("./src", &Map::new());
+        assert_eq!(warnings, vec!["Path is already used"]);
+        assert!(warnings[0].is_warning());
+        assert!(field.warnings("./free-path", &Map::new()).is_empty());
+    }
+
+    #[test]
+    fn closure_is_validator() {
+        let field = Text::new("name").validator(|data: &str| {
+            if data.starts_with('_') {
+                Err("Name can't start with _".to_string())
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(field.validate("a"), Ok(json!("a")));
+        assert_eq!(
+            field.validate("_a").unwrap_err(),
+            vec!["Name can't start with _".to_string()]
+        );
+    }
+
+    #[test]
+    fn context_validator_sees_other_values() {
+        let field =
+            Text::new("target").context_validator(|data: &str, values: &Map<String, Value>| {
+                if values.get("source") == Some(&json!(data)) {
+                    Err("Target must differ from source".to_string())
+                } else {
+                    Ok(())
+                }
+            });
+        let mut values = Map::new();
+        values.insert("source".to_string(), json!("a"));
+        assert_eq!(field.validate_in_context("b", &values), Ok(()));
+        assert_eq!(
+            field.validate_in_context("a", &values).unwrap_err(),
+            vec!["Target must differ from source".to_string()]
+        );
+    }
+}
@@ -0,0 +1,246 @@
+use clap;
+use cursive::views;
+use serde_json::map::Map;
+use serde_json::value::Value;
+
+use fields;
+use fields::{FieldErrors, WidgetManager};
+use messages::Message;
+
+const UNSET: &str = "";
+const ON: &str = "true";
+const OFF: &str = "false";
+
+/// Convienient wrapper around `Field<ToggleManager, Option<bool>>`.
+///
+/// `Toggle` covers pairs of switches like `--color` / `--no-color`.
+/// It has 3 states: unset, on, off.
+pub struct Toggle;
+
+impl Toggle {
+    /// Creates a new `Field<ToggleManager, Option<bool>>`.
+    ///
+    /// The negative switch defaults to `no-<label>`.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<IS: Into<String>>(label: IS) -> fields::Field<ToggleManager, Option<bool>> {
+        let label = label.into();
+        let mngr = ToggleManager::new(format!("no-{}", label));
+        fields::Field::new(label, mngr, None)
+    }
+}
+
+#[derive(Clone)]
+pub struct ToggleManager {
+    negative: String,
+    negative_help: String,
+}
+
+impl ToggleManager {
+    fn new(negative: String) -> Self {
+        ToggleManager {
+            negative,
+            negative_help: "".to_string(),
+        }
+    }
+}
+
+impl WidgetManager for ToggleManager {
+    fn build_widget(&self, label: &str, help: &str, initial: &str) -> views::ViewBox {
+        let view = self.build_value_view(initial);
+        fields::label_with_help_layout(view, label, help)
+    }
+    fn get_value(&self, view_box: &views::ViewBox) -> String {
+        let view_box = fields::value_view_from_layout(view_box);
+        let select: &views::SelectView<String> = (**view_box).as_any().downcast_ref().unwrap();
+        select
+            .selection()
+            .map(|x| (*x).clone())
+            .unwrap_or(UNSET.to_string())
+    }
+    fn build_value_view(&self, value: &str) -> views::ViewBox {
+        let selected = match value {
+            ON => 1,
+            OFF => 2,
+            _ => 0,
+        };
+        let select = views::SelectView::<String>::new()
+            .popup()
+            .item("unset", UNSET.to_string())
+            .item("on", ON.to_string())
+            .item("off", OFF.to_string())
+            .selected(selected);
+        views::ViewBox::new(Box::new(select))
+    }
+}
+
+impl fields::FormField for fields::Field<ToggleManager, Option<bool>> {
+    fn get_widget_manager(&self) -> &dyn WidgetManager {
+        &self.widget_manager
+    }
+
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets help of the field
+    fn get_help(&self) -> &str {
+        self.help.as_ref()
+    }
+
+    fn get_initial(&self) -> String {
+        match self.initial {
+            Some(true) => ON.to_string(),
+            Some(false) => OFF.to_string(),
+            None => UNSET.to_string(),
+        }
+    }
+
+    fn validate(&self, data: &str) -> Result<Value, FieldErrors> {
+        let (errors, _) = self.run_validators(data);
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        let value = match data {
+            UNSET => Value::Null,
+            ON => Value::Bool(true),
+            OFF => Value::Bool(false),
+            _ => return Err(vec![Message::new("toggle.invalid").into()]),
+        };
+        self.convert_value(value).map_err(|e| vec![e])
+    }
+
+    validate_in_context!();
+
+    fn warnings(&self, data: &str, values: &Map<String, Value>) -> FieldErrors {
+        self.run_warnings(data, values)
+    }
+
+    fn clap_arg(&self) -> clap::Arg<'_, '_> {
+        clap::Arg::with_name(&self.label)
+            .help(&self.help)
+            .long(&self.label)
+            .overrides_with(&self.widget_manager.negative)
+    }
+
+    fn clap_args(&self) -> Vec<clap::Arg<'_, '_>> {
+        let negative = &self.widget_manager.negative;
+        vec![
+            self.clap_arg(),
+            clap::Arg::with_name(negative)
+                .help(&self.widget_manager.negative_help)
+                .long(negative)
+                .overrides_with(&self.label),
+        ]
+    }
+
+    fn clap_args2str(&self, args: &clap::ArgMatches) -> String {
+        let v = if args.is_present(&self.label) {
+            ON
+        } else if args.is_present(&self.widget_manager.negative) {
+            OFF
+        } else {
+            UNSET
+        };
+        v.to_string()
+    }
+
+    fn dump_as_cli(&self, value: &Value) -> Vec<String> {
+        match self.revert_value(value) {
+            Value::Bool(true) => vec![format!("--{}", self.label)],
+            Value::Bool(false) => vec![format!("--{}", self.widget_manager.negative)],
+            _ => Vec::new(),
+        }
+    }
+
+    fn value2str(&self, value: &Value) -> String {
+        self.revert(value)
+    }
+
+    fn is_required(&self) -> bool {
+        self.is_required()
+    }
+}
+
+impl<W: WidgetManager> fields::Field<W, Option<bool>> {
+    /// Sets initial `value` of `field`.
+    pub fn initial(mut self, value: Option<bool>) -> Self {
+        self.initial = value;
+        self
+    }
+}
+
+impl fields::Field<ToggleManager, Option<bool>> {
+    /// Sets name of the negative switch (`no-<label>` by default).
+    pub fn negative<IS: Into<String>>(mut self, negative: IS) -> Self {
+        self.widget_manager.negative = negative.into();
+        self
+    }
+
+    /// Sets `help` message for the negative switch.
+    pub fn negative_help<IS: Into<String>>(mut self, msg: IS) -> Self {
+        self.widget_manager.negative_help = msg.into();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use converters::Converter;
+    use fields::FormField;
+    use validators::ValidationError;
+
+    #[test]
+    fn toggle_is_validated_to_null_when_unset() {
+        let field = Toggle::new("color");
+        assert_eq!(field.validate(""), Ok(Value::Null));
+        assert_eq!(field.validate("true"), Ok(Value::Bool(true)));
+        assert_eq!(field.validate("false"), Ok(Value::Bool(false)));
+    }
+
+    #[test]
+    fn toggle_is_dumped_as_positive_or_negative_switch() {
+        let field = Toggle::new("color");
+        assert_eq!(field.dump_as_cli(&Value::Bool(true)), vec!["--color"]);
+        assert_eq!(field.dump_as_cli(&Value::Bool(false)), vec!["--no-color"]);
+        assert_eq!(field.dump_as_cli(&Value::Null), Vec::<String>::new());
+    }
+
+    /// Treats unset toggle as switched off.
+    #[derive(Debug)]
+    struct OffByDefault;
+
+    impl Converter for OffByDefault {
+        fn convert(&self, value: Value) -> Result<Value, ValidationError> {
+            match value {
+                Value::Null => Ok(Value::Bool(false)),
+                other => Ok(other),
+            }
+        }
+    }
+
+    #[test]
+    fn converters_get_parsed_value() {
+        let field = Toggle::new("color").converter(OffByDefault);
+        assert_eq!(field.validate(""), Ok(Value::Bool(false)));
+        assert_eq!(field.validate("true"), Ok(Value::Bool(true)));
+    }
+
+    #[test]
+    fn toggle_negative_can_be_customized() {
+        let field = Toggle::new("color").negative("monochrome");
+        assert_eq!(field.dump_as_cli(&Value::Bool(false)), vec!["--monochrome"]);
+    }
+
+    #[test]
+    fn toggle_is_parsed_from_cli() {
+        let field = Toggle::new("color");
+        let app = clap::App::new("app").args(&field.clap_args());
+        let matches = app.clone().get_matches_from(vec!["app", "--color"]);
+        assert_eq!(field.clap_args2str(&matches), "true");
+        let matches = app.clone().get_matches_from(vec!["app", "--no-color"]);
+        assert_eq!(field.clap_args2str(&matches), "false");
+        let matches = app.get_matches_from(vec!["app"]);
+        assert_eq!(field.clap_args2str(&matches), "");
+    }
+}
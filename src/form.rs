@@ -1,20 +1,81 @@
 //! Contains form related concetps like `FormView`.
 use std::collections::HashMap;
+use std::fmt;
 use std::rc::Rc;
 
 use clap;
 use cursive::event::{Callback, Event, EventResult, Key, MouseButton, MouseEvent};
+use cursive::vec::Vec2;
 use cursive::view::{View, ViewWrapper};
 use cursive::views::{Dialog, DialogFocus, LinearLayout, ViewBox};
-use cursive::Cursive;
+use cursive::{Cursive, Printer};
 use serde_json::map::Map;
 use serde_json::value::Value;
 
 use fields::{FieldErrors, FormField};
+use messages;
+use messages::{Message, Messages};
 
 /// Container for form's errors.
 pub type FormErrors = HashMap<String, FieldErrors>;
 
+/// Name of flag making warnings errors in CLI mode.
+pub(crate) const STRICT_ARG: &str = "strict";
+
+/// Labels of options which are added to CLI by `fui` or `clap`.
+const RESERVED_LABELS: [&str; 3] = ["help", "version", STRICT_ARG];
+
+/// Problem found in definition of form (see [FormView::check]).
+///
+/// [FormView::check]: ./struct.FormView.html#method.check
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    /// Name of action with the problem (it's set by `Fui::check`).
+    pub action: Option<String>,
+    /// Label of field with the problem.
+    pub field: Option<String>,
+    /// Kind of problem (like `check.duplicate_label`).
+    pub code: String,
+    /// Description of problem.
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Creates a new `Diagnostic` described by `msg` (its key is the code).
+    pub fn new(msg: Message) -> Self {
+        Diagnostic {
+            action: None,
+            field: None,
+            code: msg.key.clone(),
+            message: msg.to_string(),
+        }
+    }
+
+    /// Sets name of action with the problem.
+    pub fn action<IS: Into<String>>(mut self, name: IS) -> Self {
+        self.action = Some(name.into());
+        self
+    }
+
+    /// Sets label of field with the problem.
+    pub fn field<IS: Into<String>>(mut self, label: IS) -> Self {
+        self.field = Some(label.into());
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref action) = self.action {
+            write!(f, "{}: ", action)?;
+        }
+        if let Some(ref field) = self.field {
+            write!(f, "--{}: ", field)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
 type OnSubmit = Option<Rc<Fn(&mut Cursive, Value)>>;
 type OnCancel = Option<Rc<Fn(&mut Cursive)>>;
 
@@ -27,6 +88,12 @@ pub struct FormView {
     fields: Vec<Box<FormField>>,
     on_submit: OnSubmit,
     on_cancel: OnCancel,
+    // Valid values of fields seen by fields using context
+    context: Map<String, Value>,
+    // Raw values of fields which `context` was found for
+    context_raw: Vec<String>,
+    // Catalog made current while form works (the default one if not set)
+    messages: Option<Rc<dyn Messages>>,
 }
 impl FormView {
     /// Creates a new `FormView` with two buttons `submit` and `cancel`.
@@ -34,13 +101,55 @@ impl FormView {
     pub fn new() -> Self {
         let layout = Dialog::new()
             .content(LinearLayout::vertical())
-            .button("Cancel", |_| {})
-            .button("Submit (Ctrl+f)", |_| {});
+            .button(messages::get("form.cancel"), |_| {})
+            .button(messages::get("form.submit"), |_| {});
         FormView {
             view: layout,
             fields: Vec::new(),
             on_submit: None,
             on_cancel: None,
+            context: Map::new(),
+            context_raw: Vec::new(),
+            messages: None,
+        }
+    }
+
+    /// Sets catalog of `messages` used by this form (see [messages]).
+    ///
+    /// Widgets of already added fields are rebuilt, so it can be set anytime
+    /// before the form is shown.
+    ///
+    /// [messages]: ../messages/index.html
+    pub fn messages<M: Messages + 'static>(mut self, messages: M) -> Self {
+        self.set_messages(Rc::new(messages));
+        self
+    }
+
+    /// Sets catalog of `messages` rebuilding widgets and buttons' labels.
+    pub(crate) fn set_messages(&mut self, messages: Rc<dyn Messages>) {
+        self.messages = Some(messages);
+        let fields = &self.fields;
+        let layout = messages::with_some(&self.messages, || {
+            let mut layout = LinearLayout::vertical();
+            for field in fields.iter() {
+                layout.add_child(field.build_widget());
+            }
+            layout
+        });
+        *self
+            .view
+            .get_content_mut()
+            .as_any_mut()
+            .downcast_mut::<LinearLayout>()
+            .unwrap() = layout;
+        // new widgets don't know values of other fields yet
+        self.context = Map::new();
+        self.context_raw.clear();
+        let labels = messages::with_some(&self.messages, || {
+            vec![messages::get("form.cancel"), messages::get("form.submit")]
+        });
+        for (button, label) in self.view.buttons_mut().zip(labels) {
+            button.set_label(label);
         }
     }
 
@@ -51,7 +160,7 @@ impl FormView {
 
     /// Appends boxed `field` to field list.
     pub fn boxed_field(mut self, field: Box<FormField>) -> Self {
-        let widget = field.build_widget();
+        let widget = messages::with_some(&self.messages, || field.build_widget());
         self.view
             .get_content_mut()
             .as_any_mut()
@@ -106,74 +215,203 @@ impl FormView {
     pub fn fields2clap_args(&self) -> Vec<clap::Arg> {
         let mut args = Vec::with_capacity(self.fields.len());
         for field in &self.fields {
-            let arg = field.clap_arg();
-            args.push(arg);
+            args.append(&mut field.clap_args());
         }
         return args;
     }
 
+    /// Translates `data` (a result of validation) to CLI arguments.
+    ///
+    /// Arguments are dumped in order of fields, each field decides about its own format.
+    pub fn dump_as_cli(&self, data: &Value) -> Vec<String> {
+        let mut args = Vec::new();
+        for field in self.fields.iter() {
+            if let Some(value) = data.get(field.get_label()) {
+                args.append(&mut field.dump_as_cli(value));
+            }
+        }
+        args
+    }
+
     /// Translates [clap::ArgMatches] to [serde_json::Value] based on fields.
     ///
+    /// Values are validated like in form, errors are returned as
+    /// [clap::Error] naming invalid flags (`error.exit()` shows them with
+    /// usage). Warnings are printed to stderr, unless flag `--strict` is
+    /// present in `arg_matches` (then they're errors).
+    ///
     /// [clap::ArgMatches]: ../../clap/struct.ArgMatches.html
+    /// [clap::Error]: ../../clap/struct.Error.html
     /// [serde_json::Value]: ../../serde_json/enum.Value.html
-    pub fn clap_arg_matches2value(&self, arg_matches: &clap::ArgMatches) -> Value {
-        let mut form_data = Map::with_capacity(self.fields.len());
+    pub fn clap_arg_matches2value(
+        &self,
+        arg_matches: &clap::ArgMatches,
+    ) -> Result<Value, clap::Error> {
+        messages::with_some(&self.messages, || self.arg_matches2value(arg_matches))
+    }
+
+    fn arg_matches2value(&self, arg_matches: &clap::ArgMatches) -> Result<Value, clap::Error> {
+        let values = self
+            .fields
+            .iter()
+            .map(|field| field.clap_args2str(arg_matches))
+            .collect::<Vec<String>>();
+        let (data, warnings) = self
+            .check_values(&values)
+            .map_err(|errors| self.clap_error(&errors, arg_matches.usage()))?;
+        if warnings.is_empty() {
+            return Ok(data);
+        }
+        if arg_matches.is_present(STRICT_ARG) {
+            return Err(self.clap_error(&warnings, arg_matches.usage()));
+        }
+        for line in self.error_lines(&warnings, "cli.warning") {
+            eprintln!("{}", line);
+        }
+        Ok(data)
+    }
+
+    /// Converts `errors` to [clap::Error] listing them in order of fields.
+    ///
+    /// [clap::Error]: ../../clap/struct.Error.html
+    fn clap_error(&self, errors: &FormErrors, usage: &str) -> clap::Error {
+        let mut description = self.error_lines(errors, "cli.invalid_value").join("\n");
+        if !usage.is_empty() {
+            description = format!("{}\n\n{}", description, usage);
+        }
+        let description = format!("{}\n\n{}", description, messages::get("cli.more_info"));
+        clap::Error::with_description(&description, clap::ErrorKind::ValueValidation)
+    }
+
+    /// Formats `errors` (in order of fields) with message `key`.
+    fn error_lines(&self, errors: &FormErrors, key: &str) -> Vec<String> {
+        let mut lines = Vec::with_capacity(errors.len());
         for field in self.fields.iter() {
-            let data = field.clap_args2str(&arg_matches);
-            match field.validate(data.as_ref()) {
-                Ok(v) => {
-                    form_data.insert(field.get_label().to_string(), v);
-                }
-                Err(e) => {
-                    let msg: Vec<String> = e.iter().map(|s| format!("ERROR: {:?}", s)).collect();
-                    eprintln!("{}", msg.join("\n"));
+            if let Some(field_errors) = errors.get(field.get_label()) {
+                for error in field_errors.iter() {
+                    let msg = Message::new(key)
+                        .param("flag", format!("--{}", field.get_label()))
+                        .param("error", &error.message);
+                    lines.push(msg.to_string());
                 }
             }
         }
-        Value::Object(form_data)
+        lines
     }
 
-    /// Validates form.
-    pub fn validate(&mut self) -> Result<Value, FormErrors> {
-        let mut data = Map::with_capacity(self.fields.len());
-        let mut errors: FormErrors = HashMap::with_capacity(self.fields.len());
+    /// Validates `values` of fields, returning valid data with warnings or errors.
+    fn check_values(&self, values: &[String]) -> Result<(Value, FormErrors), FormErrors> {
+        validate_fields(self.fields.iter().map(|f| &**f), values)
+    }
+
+    /// Returns focused part of dialog with index of focused field.
+    fn focus(&self) -> (DialogFocus, usize) {
+        let layout = self
+            .view
+            .get_content()
+            .as_any()
+            .downcast_ref::<LinearLayout>()
+            .unwrap();
+        (self.view.focus(), layout.get_focus_index())
+    }
 
+    /// Reads values of fields from their widgets.
+    fn read_values(&self) -> Vec<String> {
+        let layout = self
+            .view
+            .get_content()
+            .as_any()
+            .downcast_ref::<LinearLayout>()
+            .unwrap();
+        let mut values = Vec::with_capacity(self.fields.len());
         for (idx, field) in self.fields.iter().enumerate() {
-            let view = self
-                .view
-                .get_content()
-                .as_any()
-                .downcast_ref::<LinearLayout>()
-                .unwrap()
-                .get_child(idx)
-                .unwrap();
+            let view = layout.get_child(idx).unwrap();
             let view_box: &ViewBox = (*view).as_any().downcast_ref().unwrap();
-            let value = field.get_widget_manager().get_value(view_box);
+            values.push(field.get_widget_manager().get_value(view_box));
+        }
+        values
+    }
+
+    /// Passes valid values of fields to fields using them (like [Autocomplete::with_context]),
+    /// if values of other fields changed.
+    ///
+    /// It's done when focus moves (e.g. to other field). Only fields whose
+    /// values changed are validated.
+    ///
+    /// [Autocomplete::with_context]: ../fields/struct.Autocomplete.html#method.with_context
+    pub fn refresh_context(&mut self) {
+        if !self.fields.iter().any(|f| f.uses_context()) {
+            return;
+        }
+        let raw_values = self.read_values();
+        let mut values = self.context.clone();
+        let mut changed = Vec::new();
+        for (idx, (field, raw)) in self.fields.iter().zip(raw_values.iter()).enumerate() {
+            if self.context_raw.get(idx) == Some(raw) {
+                continue;
+            }
+            let label = field.get_label().to_owned();
+            let value = field.validate(raw).ok();
+            if value.as_ref() != values.get(&label) {
+                changed.push(label.clone());
+            }
+            match value {
+                Some(v) => values.insert(label, v),
+                None => values.remove(&label),
+            };
+        }
+        self.context_raw = raw_values;
+        if changed.is_empty() {
+            return;
+        }
+        let layout = self
+            .view
+            .get_content_mut()
+            .as_any_mut()
+            .downcast_mut::<LinearLayout>()
+            .unwrap();
+        for (idx, field) in self.fields.iter().enumerate() {
+            // own value changes while typing, it mustn't reset suggestions
             let label = field.get_label();
-            match field.validate(value.as_ref()) {
-                Ok(v) => {
-                    data.insert(label.to_owned(), v);
-                }
-                Err(e) => {
-                    errors.insert(label.to_owned(), e);
-                }
+            if field.uses_context() && changed.iter().any(|l| l != label) {
+                let view = layout.get_child_mut(idx).unwrap();
+                let viewbox: &mut ViewBox = view.as_any_mut().downcast_mut().unwrap();
+                field.set_context(viewbox, &values);
             }
         }
+        self.context = values;
+    }
 
-        if errors.is_empty() {
-            Ok(Value::Object(data))
-        } else {
-            self.show_errors(&errors);
-            Err(errors)
+    /// Validates form.
+    ///
+    /// Warnings don't make form invalid, they're only shown (see
+    /// [validate_with_warnings]).
+    ///
+    /// [validate_with_warnings]: #method.validate_with_warnings
+    pub fn validate(&mut self) -> Result<Value, FormErrors> {
+        self.validate_with_warnings().map(|(data, _)| data)
+    }
+
+    /// Validates form returning its data with warnings (or errors).
+    pub fn validate_with_warnings(&mut self) -> Result<(Value, FormErrors), FormErrors> {
+        let values = self.read_values();
+        let result = messages::with_some(&self.messages, || self.check_values(&values));
+        match result {
+            Ok((_, ref warnings)) => self.show_errors(&HashMap::new(), warnings),
+            Err(ref errors) => self.show_errors(errors, &HashMap::new()),
         }
+        result
     }
 
-    fn show_errors(&mut self, form_errors: &FormErrors) {
+    fn show_errors(&mut self, form_errors: &FormErrors, warnings: &FormErrors) {
         for (idx, field) in self.fields.iter().enumerate() {
             let label = field.get_label();
             let error = form_errors
                 .get(label)
                 .and_then(|field_errors| field_errors.first());
+            let warning = warnings
+                .get(label)
+                .and_then(|field_warnings| field_warnings.first());
             // can't call method which returns suitable view because of ownership
             //  * such method would get &mut self
             //  * self.field gets &self
@@ -191,19 +429,40 @@ impl FormView {
                 .get_child_mut(idx)
                 .unwrap();
             let viewbox: &mut ViewBox = view.as_any_mut().downcast_mut().unwrap();
-            field.set_error(viewbox, error.unwrap_or(&"".to_string()));
+            match warning {
+                Some(w) => field.set_warning(viewbox, &w.message),
+                None => field.set_error(viewbox, error.map(|e| e.message.as_str()).unwrap_or("")),
+            }
         }
     }
 
     fn event_submit(&mut self) -> EventResult {
-        match self.validate() {
-            Ok(data_map) => {
+        match self.validate_with_warnings() {
+            Ok((data_map, ref warnings)) if warnings.is_empty() => {
                 let opt_cb = self
                     .on_submit
                     .clone()
                     .map(|cb| Callback::from_fn(move |c| cb(c, data_map.clone())));
                 EventResult::Consumed(opt_cb)
             }
+            Ok((data_map, warnings)) => {
+                let text = self.error_lines(&warnings, "form.warning").join("\n");
+                let on_submit = self.on_submit.clone();
+                EventResult::with_cb(move |c| {
+                    let on_submit = on_submit.clone();
+                    let data_map = data_map.clone();
+                    let dialog = Dialog::text(text.clone())
+                        .title(messages::get("form.warnings"))
+                        .dismiss_button(messages::get("dialog.cancel"))
+                        .button(messages::get("form.submit_anyway"), move |c| {
+                            c.pop_layer();
+                            if let Some(ref cb) = on_submit {
+                                cb(c, data_map.clone());
+                            }
+                        });
+                    c.add_layer(dialog);
+                })
+            }
             Err(_) => {
                 // TODO: the event focus next required/invalid field?
                 EventResult::Consumed(None)
@@ -225,6 +484,30 @@ impl FormView {
         self
     }
 
+    /// Finds problems in definition of the form, like duplicated labels or invalid initial values.
+    ///
+    /// It doesn't need terminal, so it can be run in unit tests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fui::fields::{Checkbox, Text};
+    /// use fui::form::FormView;
+    /// use fui::validators::Required;
+    ///
+    /// let form = FormView::new()
+    ///     .field(Text::new("name"))
+    ///     .field(Text::new("name"))
+    ///     .field(Checkbox::new("force").validator(Required));
+    /// let codes = form.check().into_iter().map(|d| d.code).collect::<Vec<String>>();
+    /// assert_eq!(codes, vec!["check.duplicate_label", "check.required_checkbox"]);
+    /// ```
+    pub fn check(&self) -> Vec<Diagnostic> {
+        messages::with_some(&self.messages, || {
+            check_fields(self.fields.iter().map(|f| &**f))
+        })
+    }
+
     /// Gets fields of `FormView`
     pub fn get_fields(&self) -> &[Box<FormField>] {
         &self.fields
@@ -254,10 +537,8 @@ impl FormView {
     }
 }
 
-impl ViewWrapper for FormView {
-    wrap_impl!(self.view: Dialog);
-
-    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+impl FormView {
+    fn handle_event(&mut self, event: Event) -> EventResult {
         match event {
             Event::Mouse {
                 offset: _,
@@ -293,3 +574,128 @@ impl ViewWrapper for FormView {
         }
     }
 }
+
+impl ViewWrapper for FormView {
+    wrap_impl!(self.view: Dialog);
+
+    fn wrap_draw(&self, printer: &Printer) {
+        messages::with_some(&self.messages, || self.view.draw(printer));
+    }
+
+    fn wrap_layout(&mut self, size: Vec2) {
+        let catalog = self.messages.clone();
+        messages::with_some(&catalog, || self.view.layout(size));
+    }
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        let catalog = self.messages.clone();
+        let result = messages::with_some(&catalog, || {
+            let focus = self.focus();
+            let result = self.handle_event(event);
+            // refreshing on every key would validate while typing
+            if self.focus() != focus {
+                self.refresh_context();
+            }
+            result
+        });
+        match result {
+            // callbacks (like popups of fields) use form's catalog too
+            EventResult::Consumed(Some(cb)) if catalog.is_some() => {
+                EventResult::with_cb(move |c| messages::with_some(&catalog, || cb(c)))
+            }
+            other => other,
+        }
+    }
+}
+
+/// Sets `label` as field of `errors`.
+fn with_field(errors: FieldErrors, label: &str) -> FieldErrors {
+    errors.into_iter().map(|e| e.field(label)).collect()
+}
+
+/// Validates `values` of `fields` (in the same order), returning valid data
+/// with warnings or errors.
+///
+/// Context validators run after all fields, so they see values of valid ones.
+pub(crate) fn validate_fields<'a, I>(
+    fields: I,
+    values: &[String],
+) -> Result<(Value, FormErrors), FormErrors>
+where
+    I: Iterator<Item = &'a dyn FormField> + Clone,
+{
+    let mut data = Map::with_capacity(values.len());
+    let mut errors: FormErrors = HashMap::with_capacity(values.len());
+    for (field, value) in fields.clone().zip(values.iter()) {
+        let label = field.get_label();
+        match field.validate(value.as_ref()) {
+            Ok(v) => {
+                data.insert(label.to_owned(), v);
+            }
+            Err(e) => {
+                errors.insert(label.to_owned(), with_field(e, label));
+            }
+        }
+    }
+    // context validators see values of all valid fields
+    for (field, value) in fields.clone().zip(values.iter()) {
+        let label = field.get_label();
+        if errors.contains_key(label) {
+            continue;
+        }
+        if let Err(e) = field.validate_in_context(value, &data) {
+            errors.insert(label.to_owned(), with_field(e, label));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    let mut warnings: FormErrors = HashMap::new();
+    for (field, value) in fields.zip(values.iter()) {
+        let label = field.get_label();
+        let field_warnings = field.warnings(value, &data);
+        if !field_warnings.is_empty() {
+            warnings.insert(label.to_owned(), with_field(field_warnings, label));
+        }
+    }
+    Ok((Value::Object(data), warnings))
+}
+
+/// Finds problems in definitions of `fields` (see [FormView::check]).
+///
+/// [FormView::check]: ./struct.FormView.html#method.check
+pub(crate) fn check_fields<'a, I>(fields: I) -> Vec<Diagnostic>
+where
+    I: Iterator<Item = &'a dyn FormField>,
+{
+    let mut diagnostics = Vec::new();
+    let mut seen = Vec::new();
+    for field in fields {
+        let label = field.get_label();
+        if seen.contains(&label) {
+            let msg = Message::new("check.duplicate_label").param("label", format!("{:?}", label));
+            diagnostics.push(Diagnostic::new(msg).field(label));
+        }
+        seen.push(label);
+        if !is_cli_name(label) {
+            let msg = Message::new("check.invalid_label").param("label", format!("{:?}", label));
+            diagnostics.push(Diagnostic::new(msg).field(label));
+        } else if RESERVED_LABELS.contains(&label) {
+            let msg = Message::new("check.reserved_label").param("label", label);
+            diagnostics.push(Diagnostic::new(msg).field(label));
+        }
+        for diagnostic in field.check().into_iter() {
+            if diagnostic.field.is_some() {
+                diagnostics.push(diagnostic);
+            } else {
+                diagnostics.push(diagnostic.field(label));
+            }
+        }
+    }
+    diagnostics
+}
+
+/// Checks if `name` can be used as CLI option (or subcommand) without quoting.
+pub(crate) fn is_cli_name(name: &str) -> bool {
+    !name.is_empty() && !name.starts_with('-') && !name.chars().any(|c| c.is_whitespace())
+}
@@ -12,6 +12,12 @@ extern crate clipboard;
 extern crate cursive as _cursive;
 extern crate dirs;
 extern crate glob;
+#[cfg(unix)]
+extern crate libc;
+#[cfg(feature = "ncurses-backend")]
+extern crate ncurses;
+#[cfg(feature = "pancurses-backend")]
+extern crate pancurses;
 extern crate regex;
 #[macro_use]
 extern crate serde_json;
@@ -23,14 +29,20 @@ mod clap_conv;
 pub mod cursive {
     pub use _cursive::*;
 }
+pub use serde_json::map::Map;
 pub use serde_json::value::Value;
+pub mod converters;
 pub mod feeders;
 pub mod fields;
 pub mod form;
+pub mod messages;
 pub mod utils;
 pub mod validators;
 pub mod views;
 
+#[cfg(test)]
+mod test_utils;
+
 use clipboard::ClipboardContext;
 use clipboard::ClipboardProvider;
 use cursive::event::Event;
@@ -38,8 +50,10 @@ use cursive::traits::{Boxable, Identifiable};
 use cursive::view::Scrollable;
 use cursive::views::{Dialog, LayerPosition, OnEventView};
 use cursive::Cursive;
+use feeders::{Items, Suggestion};
 use fields::autocomplete::AutocompleteManager;
 use form::FormView;
+use messages::{Message, Messages};
 use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::env;
@@ -61,12 +75,6 @@ struct Action<'action> {
     handler: Rc<Fn(Value)>,
 }
 
-impl<'action> Action<'action> {
-    fn cmd_with_desc(&self) -> String {
-        format!("{}: {}", self.name, self.help)
-    }
-}
-
 fn value2array(value: &Value) -> Vec<String> {
     let mut result: Vec<String> = Vec::new();
     if let Value::Object(map) = value {
@@ -83,7 +91,7 @@ fn value2array(value: &Value) -> Vec<String> {
                     result.push(format!("{}", n));
                 }
                 Value::String(s) => {
-                    if  val != "" {
+                    if val != "" {
                         if !key_is_digit {
                             result.push(format!("--{}", key));
                         }
@@ -112,24 +120,18 @@ fn value2array(value: &Value) -> Vec<String> {
     result
 }
 
-trait DumpAsCli {
-    fn dump_as_cli(&self) -> String;
-}
-
-impl DumpAsCli for Value {
-    fn dump_as_cli(&self) -> String {
-        return value2array(&self)
-            .iter()
-            .map(|a| {
-                if a.contains(" ") {
-                    format!("\"{}\"", a)
-                } else {
-                    format!("{}", a)
-                }
-            })
-            .collect::<Vec<String>>()
-            .join(" ");
-    }
+/// Joins `args` to a single command, quoting the ones with spaces.
+fn args2cmd(args: &[String]) -> String {
+    args.iter()
+        .map(|a| {
+            if a.contains(" ") {
+                format!("\"{}\"", a)
+            } else {
+                a.to_string()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
 }
 
 /// Top level building block of `fui` crate.
@@ -144,12 +146,18 @@ pub struct Fui<'attrs, 'action> {
     theme: &'attrs str,
     picked_action: Rc<RefCell<Option<String>>>,
     form_data: Rc<RefCell<Option<Value>>>,
+    /// `form_data` dumped by the submitted form
+    form_args: Rc<RefCell<Option<Vec<String>>>>,
     /// if true skips action selection in tui, auto choosing the only action
     skip_single_action: bool,
     /// if true form step is skipped when form has no fields
     skip_empty_form: bool,
     /// Holds active step in wizard
     active_step: Rc<RefCell<u8>>,
+    /// help of `--strict` flag (from the current catalog of messages)
+    strict_help: String,
+    /// catalog of messages used by forms & CLI (the default one if not set)
+    messages: Option<Rc<dyn Messages>>,
 }
 impl<'attrs, 'action> Fui<'attrs, 'action> {
     /// Creates a new `Fui` with empty actions.
@@ -164,9 +172,12 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
             theme: &DEFAULT_THEME,
             picked_action: Rc::new(RefCell::new(None)),
             form_data: Rc::new(RefCell::new(None)),
+            form_args: Rc::new(RefCell::new(None)),
             skip_single_action: false,
             skip_empty_form: false,
             active_step: Rc::new(RefCell::new(1)),
+            strict_help: messages::get("cli.strict"),
+            messages: None,
         }
     }
     /// Defines action by providing `name`, `help`, `form`, `hdlr`.
@@ -192,6 +203,10 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
     where
         F: Fn(Value) + 'static,
     {
+        let mut form = form;
+        if let Some(ref messages) = self.messages {
+            form.set_messages(messages.clone());
+        }
         let action_details = Action {
             name: name,
             help: help,
@@ -201,8 +216,8 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
 
         if let Some(item) = self.action_by_name(&name) {
             panic!(
-                "Action name must be unique, but it's already defined ({:?})",
-                item.cmd_with_desc()
+                "Action name must be unique, but it's already defined ({:?}: {:?})",
+                item.name, item.help
             );
         }
         // it's used when deciding to skip empty forms
@@ -211,12 +226,47 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
         self.form_fields_count
             .insert(action_details.name, fields_count as u8);
         self.actions
-            .insert(action_details.cmd_with_desc(), action_details);
+            .insert(action_details.name.to_string(), action_details);
         self
     }
 
+    /// Finds problems in definitions of actions and their forms.
+    ///
+    /// Call it in a unit test, so bad definitions fail before user sees them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fui::fields::Text;
+    /// use fui::form::FormView;
+    /// use fui::Fui;
+    ///
+    /// let app = Fui::new("app")
+    ///     .action("ok", "desc", FormView::new().field(Text::new("name")), |_| {})
+    ///     .action("bad", "desc", FormView::new().field(Text::new("full name")), |_| {});
+    /// let found = app.check().iter().map(|d| d.to_string()).collect::<Vec<String>>();
+    /// assert_eq!(found.len(), 1);
+    /// assert!(found[0].starts_with("bad: --full name: Label \"full name\" can't be a CLI option"));
+    /// ```
+    pub fn check(&self) -> Vec<form::Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for action in self.actions.values() {
+            if !form::is_cli_name(action.name) {
+                let msg = Message::new("check.invalid_action")
+                    .param("name", format!("{:?}", action.name));
+                diagnostics.push(form::Diagnostic::new(msg).action(action.name));
+            }
+            if let Some(ref form) = action.form {
+                for diagnostic in form.check().into_iter() {
+                    diagnostics.push(diagnostic.action(action.name));
+                }
+            }
+        }
+        diagnostics
+    }
+
     fn action_by_name(&self, name: &str) -> Option<&Action> {
-        self.actions.values().find(|a| a.name == name)
+        self.actions.get(name)
     }
 
     /// Coordinates flow from action picking to handler running.
@@ -224,13 +274,16 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
     // then top layer are switched (instead of current inserting/popping)
     pub fn run(mut self) {
         let args = env::args_os();
-        let input_data = if args.len() > 1 {
-            // input from CLI
-            self.input_from_cli(args)
-        } else {
-            // input from TUI
-            self.input_from_tui()
-        };
+        let catalog = self.messages.clone();
+        let input_data = messages::with_some(&catalog, || {
+            if args.len() > 1 {
+                // input from CLI
+                self.input_from_cli(args)
+            } else {
+                // input from TUI
+                self.input_from_tui()
+            }
+        });
         // run handler
         if let Some((action_name, data)) = input_data {
             if let Some(action) = self.action_by_name(&action_name) {
@@ -247,7 +300,9 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
                 arg_vec.push(a.to_owned())
             }
         }
-        if let Some(f) = self.form_data.borrow().as_ref() {
+        if let Some(args) = self.form_args.borrow().as_ref() {
+            arg_vec.extend(args.iter().cloned());
+        } else if let Some(f) = self.form_data.borrow().as_ref() {
             arg_vec.append(&mut value2array(&f));
         }
         arg_vec
@@ -272,7 +327,8 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
 
     /// Gets user input converted to cli-like format
     pub fn get_cli_input(mut self) -> Vec<String> {
-        self.input_from_tui();
+        let catalog = self.messages.clone();
+        messages::with_some(&catalog, || self.input_from_tui());
         self.dump_as_cli()
     }
 
@@ -288,10 +344,15 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
                 .args(args.as_slice());
             sub_cmds.push(sub_cmd);
         }
-        clap::App::new(self.name.as_ref())
+        let strict = clap::Arg::with_name(form::STRICT_ARG)
+            .long(form::STRICT_ARG)
+            .help(self.strict_help.as_ref())
+            .global(true);
+        clap::App::new(self.name)
             .version(self.version.as_ref())
             .about(self.about.as_ref())
             .author(self.author.as_ref())
+            .arg(strict)
             .subcommands(sub_cmds)
     }
 
@@ -310,17 +371,14 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
         let matches = app.get_matches_from(user_args);
         let cmd_name = matches.subcommand_name().unwrap();
         let cmd_matches = matches.subcommand_matches(cmd_name).unwrap();
-        let action = self
-            .actions
-            .values()
-            .find(|action| action.name == cmd_name)
-            .unwrap();
+        let action = self.action_by_name(cmd_name).unwrap();
         let value = action
             .form
             .as_ref()
             .unwrap()
-            .clap_arg_matches2value(cmd_matches);
-        Some((action.cmd_with_desc(), value))
+            .clap_arg_matches2value(cmd_matches)
+            .unwrap_or_else(|e| e.exit());
+        Some((action.name.to_string(), value))
     }
 
     fn header(&self) -> String {
@@ -334,12 +392,16 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
         return header;
     }
 
-    fn set_form_events(&self, form: &mut FormView) {
+    fn set_form_events(&self, form: &mut FormView, form_id: &str) {
         // set form events
         let form_data = Rc::clone(&self.form_data);
+        let form_args = Rc::clone(&self.form_args);
+        let form_id = form_id.to_owned();
         let step_submit = Rc::clone(&self.active_step);
         let step_cancel = Rc::clone(&self.active_step);
         form.set_on_submit(move |c: &mut Cursive, data: Value| {
+            *form_args.borrow_mut() =
+                c.call_on_id(&form_id, |form: &mut FormView| form.dump_as_cli(&data));
             *form_data.borrow_mut() = Some(data);
             *step_submit.borrow_mut() += 1;
             c.quit();
@@ -358,18 +420,19 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
         let form = OnEventView::new(form).on_event(Event::CtrlChar('k'), move |c| {
             let err = c.call_on_id(&form_id, |form: &mut FormView| match form.validate() {
                 Ok(s) => {
-                    let msg = format!("{} {} {}", prog_name, form_id, s.dump_as_cli());
+                    let args = args2cmd(&form.dump_as_cli(&s));
+                    let msg = format!("{} {} {}", prog_name, form_id, args);
                     let mut ctx: ClipboardContext = ClipboardProvider::new().unwrap();
                     ctx.set_contents(msg).unwrap();
                     Ok(())
                 }
                 Err(_) => {
-                    let err = format!("Copying to clipboard - FAILED.\nFix form errors first.");
+                    let err = messages::get("fui.clipboard_failed");
                     Err(err)
                 }
             });
             if let Err(e) = err.unwrap() {
-                c.add_layer(Dialog::info(e).title("Form invalid!"));
+                c.add_layer(Dialog::info(e).title(messages::get("fui.form_invalid")));
             }
         });
         c.add_layer(form);
@@ -383,7 +446,7 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
             .map(|(_, a)| (a.name, a.form.take().unwrap()))
             .collect::<Vec<(&str, FormView)>>();
         for (form_id, mut form) in action_form_list.into_iter() {
-            self.set_form_events(&mut form);
+            self.set_form_events(&mut form, form_id);
             self.add_form(c, form, form_id);
         }
     }
@@ -392,13 +455,13 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
         let cmd_submit = Rc::clone(&self.picked_action);
         let step_submit = Rc::clone(&self.active_step);
         let step_cancel = Rc::clone(&self.active_step);
-        // TODO: rm cloning for it
-        let actions = self
-            .actions
-            .keys()
-            .map(|x| x.to_owned())
-            .collect::<Vec<String>>();
-        let feeder = actions.clone();
+        let actions = self.actions.keys().cloned().collect::<Vec<String>>();
+        let feeder = Items::from_suggestions(
+            self.actions
+                .values()
+                .map(|a| Suggestion::new(a.name).description(a.help).kind("action"))
+                .collect(),
+        );
         let mngr = AutocompleteManager::with_factory_view(Rc::new(move || {
             Autocomplete::new(feeder.clone()).shown_count(12)
         }));
@@ -406,14 +469,11 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
             .title(&self.header())
             .field(
                 fields::Field::new("action", mngr, "".to_string())
-                    .help("Pick action")
+                    .help(messages::get("fui.pick_action"))
                     .validator(OneOf(actions)),
             )
             .on_submit(move |c, data| {
                 let value = data.get("action").unwrap().clone();
-                // here we return name+desc of Action, we can't return only name
-                // because Action has shorter lifetime then this callback (which is static)
-                // so thanks to lifetime, they saved me a bug :)
                 *cmd_submit.borrow_mut() = Some(value.as_str().unwrap().to_string());
                 *step_submit.borrow_mut() += 1;
                 c.quit();
@@ -452,39 +512,37 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
         self.add_forms(&mut c);
         self.add_cmd_picker(&mut c);
         loop {
-            let current_step =  *self.active_step.borrow();
+            let current_step = *self.active_step.borrow();
             match current_step {
                 0 => ::std::process::exit(0),
                 1 => {
                     // show action picker
                     if self.skip_single_action && self.actions.len() < 2 {
                         // skip action picker by auto pick only item
-                        let action_with_desc = self.actions.keys().nth(0).unwrap().clone();
-                        *self.picked_action.borrow_mut() = Some(action_with_desc);
+                        let action_name = self.actions.keys().nth(0).unwrap().clone();
+                        *self.picked_action.borrow_mut() = Some(action_name);
                         *self.active_step.borrow_mut() = 2;
                         continue;
                     }
                     self.top_layer_by_name(&mut c, COMMAND_PICKER_ID);
-                },
+                }
                 2 => {
                     // show form
-                    let action_with_desc = match self.picked_action.borrow().clone() {
+                    let action_name = match self.picked_action.borrow().clone() {
                         Some(v) => v,
                         None => {
                             *self.active_step.borrow_mut() = 1;
                             continue;
-                        },
+                        }
                     };
-                    // to get action name we have to extract it from "name: desc"
-                    let action_name = self.actions.get(&action_with_desc).unwrap().name;
-                    *self.picked_action.borrow_mut() = Some(action_name.to_string());
+                    let action_name = self.actions.get(&action_name).unwrap().name;
                     if !self.has_form_fields(&action_name) {
                         *self.form_data.borrow_mut() = Some(json!({}));
                         *self.active_step.borrow_mut() = 3;
                         continue;
                     }
                     self.top_layer_by_name(&mut c, action_name);
-                },
+                }
                 3 => break,
                 _ => unimplemented!(),
             }
@@ -606,6 +664,25 @@ impl<'attrs, 'action> Fui<'attrs, 'action> {
         self.skip_empty_form = skip;
         self
     }
+
+    /// Sets catalog of `messages` shown to user (see [messages]).
+    ///
+    /// It's passed to forms of actions (see [FormView::messages]), no matter
+    /// if they're added before or after it.
+    ///
+    /// [messages]: ./messages/index.html
+    /// [FormView::messages]: ./form/struct.FormView.html#method.messages
+    pub fn messages<M: Messages + 'static>(mut self, messages: M) -> Self {
+        let messages: Rc<dyn Messages> = Rc::new(messages);
+        self.strict_help = messages::with(&messages, || messages::get("cli.strict"));
+        for action in self.actions.values_mut() {
+            if let Some(ref mut form) = action.form {
+                form.set_messages(messages.clone());
+            }
+        }
+        self.messages = Some(messages);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -624,7 +701,7 @@ mod test_date_getting_from_program_args {
             .input_from_cli(vec!["my_app", "action1", "--ch1"]);
 
         let exp: Value = serde_json::from_str(r#"{ "ch1": true }"#).unwrap();
-        assert_eq!(value, Some(("action1: desc".to_string(), exp)));
+        assert_eq!(value, Some(("action1".to_string(), exp)));
     }
 
     #[test]
@@ -639,7 +716,7 @@ mod test_date_getting_from_program_args {
             .input_from_cli(vec!["my_app", "action1"]);
 
         let exp: Value = serde_json::from_str(r#"{ "ch1": false }"#).unwrap();
-        assert_eq!(value, Some(("action1: desc".to_string(), exp)));
+        assert_eq!(value, Some(("action1".to_string(), exp)));
     }
 
     #[test]
@@ -654,7 +731,7 @@ mod test_date_getting_from_program_args {
             .input_from_cli(vec!["my_app", "action1", "--t1", "v1"]);
 
         let exp: Value = serde_json::from_str(r#"{ "t1": "v1" }"#).unwrap();
-        assert_eq!(value, Some(("action1: desc".to_string(), exp)));
+        assert_eq!(value, Some(("action1".to_string(), exp)));
     }
 
     //#[test]
@@ -674,7 +751,7 @@ mod test_date_getting_from_program_args {
             .input_from_cli(vec!["my_app", "action1", "--ac", "v1"]);
 
         let exp: Value = serde_json::from_str(r#"{ "ac": "v1" }"#).unwrap();
-        assert_eq!(value, Some(("action1: desc".to_string(), exp)));
+        assert_eq!(value, Some(("action1".to_string(), exp)));
     }
 
     //#[test]
@@ -693,13 +770,227 @@ mod test_date_getting_from_program_args {
             )
             .input_from_cli(vec!["my_app", "action1", "--mf", "v1"]);
         let exp: Value = serde_json::from_str(r#"{ "mf": ["v1"] }"#).unwrap();
-        assert_eq!(value, Some(("action1: desc".to_string(), exp)));
+        assert_eq!(value, Some(("action1".to_string(), exp)));
     }
 
     //#[test]
     //fn cli_multiselect_is_serialized_ok_when_value_missing() {
     //    // clap blocks this case, optionally test ensuring that
     //}
+
+    #[test]
+    fn cli_picked_action_has_handler() {
+        let called = Rc::new(RefCell::new(None));
+        let called_in_handler = Rc::clone(&called);
+        let app = Fui::new("app").action(
+            "action1",
+            "desc",
+            FormView::new().field(fields::Text::new("t1")),
+            move |v| *called_in_handler.borrow_mut() = Some(v),
+        );
+        let (name, value) = app
+            .input_from_cli(vec!["my_app", "action1", "--t1", "v1"])
+            .unwrap();
+        (app.action_by_name(&name).unwrap().handler)(value);
+        assert_eq!(*called.borrow(), Some(json!({ "t1": "v1" })));
+    }
+
+    #[test]
+    fn cli_invalid_value_is_clap_error_naming_flag() {
+        let fui = Fui::new("app").action(
+            "action1",
+            "desc",
+            FormView::new()
+                .field(fields::Text::new("port").validator(validators::Port))
+                .field(fields::Text::new("host").validator(validators::Hostname)),
+            |_| {},
+        );
+        let args = vec!["my_app", "action1", "--port", "0", "--host", "a..b"];
+        let matches = fui.build_cli_app().get_matches_from(args);
+        let cmd_matches = matches.subcommand_matches("action1").unwrap();
+        let form = fui.actions()[0].form.as_ref().unwrap();
+
+        let err = form.clap_arg_matches2value(cmd_matches).unwrap_err();
+        assert_eq!(err.kind, clap::ErrorKind::ValueValidation);
+        let expected = concat!(
+            "Invalid value for '--port': Value \"0\" is not a port (1-65535)\n",
+            "Invalid value for '--host': Value \"a..b\" is not a host name\n\n",
+            "USAGE:"
+        );
+        assert!(err.message.contains(expected), "{}", err.message);
+    }
+
+    #[test]
+    fn messages_are_used_by_forms_of_fui_only() {
+        let polish = messages::Table::from_json(
+            r#"{"validators.port": "Niepoprawny port", "form.cancel": "Anuluj"}"#,
+        )
+        .unwrap();
+        let fui = Fui::new("app")
+            .action(
+                "action1",
+                "desc",
+                FormView::new().field(fields::Text::new("port").validator(validators::Port)),
+                |_| {},
+            )
+            .messages(polish);
+        let args = vec!["my_app", "action1", "--port", "0"];
+        let matches = fui.build_cli_app().get_matches_from(args);
+        let cmd_matches = matches.subcommand_matches("action1").unwrap();
+        let form = fui.actions()[0].form.as_ref().unwrap();
+
+        let err = form.clap_arg_matches2value(cmd_matches).unwrap_err();
+        assert!(err.message.contains("Niepoprawny port"), "{}", err.message);
+        assert_eq!(messages::get("form.cancel"), "Cancel");
+    }
+
+    #[test]
+    fn cli_warnings_are_errors_only_when_strict() {
+        let fui = Fui::new("app").action(
+            "action1",
+            "desc",
+            FormView::new()
+                .field(fields::Text::new("out").validator(validators::Warn(validators::PathFree))),
+            |_| {},
+        );
+        let form = fui.actions()[0].form.as_ref().unwrap();
+
+        let matches = fui
+            .build_cli_app()
+            .get_matches_from(vec!["my_app", "action1", "--out", "./src"]);
+        let cmd_matches = matches.subcommand_matches("action1").unwrap();
+        assert_eq!(
+            form.clap_arg_matches2value(cmd_matches).unwrap(),
+            json!({"out": "./src"})
+        );
+
+        let matches = fui
+            .build_cli_app()
+            .get_matches_from(vec!["my_app", "action1", "--out", "./src", "--strict"]);
+        let cmd_matches = matches.subcommand_matches("action1").unwrap();
+        let err = form.clap_arg_matches2value(cmd_matches).unwrap_err();
+        assert!(err
+            .message
+            .contains("Invalid value for '--out': Path is already used"));
+    }
+}
+
+#[cfg(test)]
+mod check_definitions {
+    use super::*;
+    use fields::{Autocomplete, Checkbox, Multiselect, SubForm, Text, Toggle};
+    use validators::{OneOf, Required};
+
+    fn codes(app: &Fui) -> Vec<String> {
+        app.check().into_iter().map(|d| d.code).collect()
+    }
+
+    #[test]
+    fn valid_definitions_have_no_diagnostics() {
+        let form = FormView::new()
+            .field(Text::new("name").validator(Required))
+            .field(Autocomplete::new("level", vec!["debug", "info"]).initial("info"))
+            .field(Multiselect::new("tags", vec!["a", "b"]).validator(Required))
+            .field(Checkbox::new("force").initial(true))
+            .field(Toggle::new("color"))
+            .field(SubForm::repeatable("ports").field(Text::new("host").validator(Required)));
+        let app = Fui::new("app").action("action1", "desc", form, |_| {});
+        assert_eq!(app.check(), vec![]);
+    }
+
+    #[test]
+    fn invalid_initial_is_found() {
+        let form = FormView::new().field(
+            Autocomplete::new("level", vec!["debug", "info"])
+                .initial("trace")
+                .validator(OneOf(vec!["debug", "info"])),
+        );
+        let app = Fui::new("app").action("action1", "desc", form, |_| {});
+        let found = app.check();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].action, Some("action1".to_string()));
+        assert_eq!(found[0].field, Some("level".to_string()));
+        assert_eq!(
+            found[0].message,
+            "Initial value \"trace\" is invalid: Value must be one of options"
+        );
+    }
+
+    #[test]
+    fn labels_unusable_in_cli_are_found() {
+        let form = FormView::new()
+            .field(Text::new("strict"))
+            .field(Text::new("-x"))
+            .field(
+                SubForm::new("db")
+                    .field(Text::new("a"))
+                    .field(Text::new("a")),
+            );
+        let app = Fui::new("app").action("my action", "desc", form, |_| {});
+        assert_eq!(
+            codes(&app),
+            vec![
+                "check.invalid_action",
+                "check.reserved_label",
+                "check.invalid_label",
+                "check.duplicate_label",
+            ]
+        );
+        assert_eq!(app.check()[3].field, Some("db.a".to_string()));
+    }
+}
+
+#[cfg(test)]
+mod context_feeders {
+    use super::*;
+    use fields::Text;
+
+    #[test]
+    fn dependent_field_gets_values_of_other_fields() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen_by_feeder = Rc::clone(&seen);
+        let branches = move |values: &Map<String, Value>| {
+            seen_by_feeder
+                .borrow_mut()
+                .push(values.get("repo").cloned());
+            vec!["master".to_string()]
+        };
+        let mut form = FormView::new()
+            .field(Text::new("repo").initial("fui"))
+            .field(fields::Autocomplete::with_context("branch", branches));
+        // suggestions of new widget are queried without values
+        assert!(seen.borrow().iter().all(Option::is_none));
+        let queried = seen.borrow().len();
+
+        form.refresh_context();
+        assert_eq!(seen.borrow().len(), queried + 1);
+        assert_eq!(seen.borrow()[queried], Some(Value::from("fui")));
+
+        // values didn't change
+        form.refresh_context();
+        assert_eq!(seen.borrow().len(), queried + 1);
+    }
+
+    #[test]
+    fn only_changed_values_are_validated_for_context() {
+        let validated = Rc::new(RefCell::new(0));
+        let counter = Rc::clone(&validated);
+        let count = move |_: &str| -> Result<(), String> {
+            *counter.borrow_mut() += 1;
+            Ok(())
+        };
+        let mut form = FormView::new()
+            .field(Text::new("repo").initial("fui").validator(count))
+            .field(fields::Autocomplete::with_context(
+                "branch",
+                |_: &Map<String, Value>| vec!["master".to_string()],
+            ));
+
+        form.refresh_context();
+        assert_eq!(*validated.borrow(), 1);
+        form.refresh_context();
+        assert_eq!(*validated.borrow(), 1);
+    }
 }
 
 #[cfg(test)]
@@ -709,25 +1000,25 @@ mod dump_as_cli {
     #[test]
     fn test_value_is_converted_to_cmd_ok_when_is_string() {
         let v: Value = serde_json::from_str(r#"{ "arg": "abc" }"#).unwrap();
-        assert_eq!(v.dump_as_cli(), r#"--arg abc"#);
+        assert_eq!(args2cmd(&value2array(&v)), r#"--arg abc"#);
     }
 
     #[test]
     fn test_value_string_includes_quotes_when_include_space() {
         let v: Value = serde_json::from_str(r#"{ "arg": "a b" }"#).unwrap();
-        assert_eq!(v.dump_as_cli(), r#"--arg "a b""#);
+        assert_eq!(args2cmd(&value2array(&v)), r#"--arg "a b""#);
     }
 
     #[test]
     fn test_value_is_converted_to_cmd_ok_when_is_array() {
         let v: Value = serde_json::from_str(r#"{ "arg": ["a", "b c"] }"#).unwrap();
-        assert_eq!(v.dump_as_cli(), r#"--arg a "b c""#);
+        assert_eq!(args2cmd(&value2array(&v)), r#"--arg a "b c""#);
     }
 
     #[test]
     fn test_value_is_empty_when_arg_is_false() {
         let v: Value = serde_json::from_str(r#"{ "arg": false }"#).unwrap();
-        assert_eq!(v.dump_as_cli(), r#""#);
+        assert_eq!(args2cmd(&value2array(&v)), r#""#);
     }
 }
 
@@ -0,0 +1,398 @@
+//! Provides translatable texts shown to user.
+//!
+//! Texts are identified by keys (like `validators.required`) and may include
+//! parameters (like `{value}`). Catalogs are kept by [Fui::messages] and
+//! [FormView::messages], which make them current while they build widgets,
+//! handle events or validate. Outside of them the current catalog is the
+//! default one: [English] unless it's replaced by [set].
+//!
+//! [English]: ./struct.English.html
+//! [set]: ./fn.set.html
+//! [Fui::messages]: ../struct.Fui.html#method.messages
+//! [FormView::messages]: ../form/struct.FormView.html#method.messages
+use serde_json;
+use serde_json::value::Value;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::mem;
+use std::rc::Rc;
+
+thread_local! {
+    static CURRENT: RefCell<Rc<dyn Messages>> = RefCell::new(Rc::new(English));
+}
+
+/// Catalog of message templates.
+pub trait Messages {
+    /// Returns template of message with `key` (or `None` if it's unknown).
+    ///
+    /// Template's parameters are written in braces, like `{value}`.
+    fn template(&self, key: &str) -> Option<String>;
+}
+
+/// Message identified by `key` with `params`.
+///
+/// It's rendered with the current catalog by `to_string`.
+/// Key which is missing in catalog is rendered as is, so any text can be a key.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Message {
+    /// Key of message in catalog.
+    pub key: String,
+    /// Values of template's parameters.
+    pub params: Vec<(String, String)>,
+    raw: bool,
+}
+
+impl Message {
+    /// Creates a new `Message` with `key`.
+    pub fn new<IS: Into<String>>(key: IS) -> Self {
+        Message {
+            key: key.into(),
+            params: Vec::new(),
+            raw: false,
+        }
+    }
+
+    /// Creates a new `Message` with `text` which isn't looked up in catalog.
+    pub fn raw<IS: Into<String>>(text: IS) -> Self {
+        Message {
+            raw: true,
+            ..Message::new(text)
+        }
+    }
+
+    /// Checks if this message is a text shown as is (see [raw]).
+    ///
+    /// [raw]: #method.raw
+    pub fn is_raw(&self) -> bool {
+        self.raw
+    }
+
+    /// Sets parameter `name` to `value`.
+    pub fn param<IS: Into<String>, D: fmt::Display>(mut self, name: IS, value: D) -> Self {
+        self.params.push((name.into(), value.to_string()));
+        self
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.raw {
+            return write!(f, "{}", self.key);
+        }
+        let mut text = template(&self.key);
+        for (name, value) in self.params.iter() {
+            text = text.replace(&format!("{{{}}}", name), value);
+        }
+        write!(f, "{}", text)
+    }
+}
+
+/// Sets `messages` as the default catalog (of this thread).
+///
+/// Keys missing in `messages` fall back to [English].
+///
+/// [English]: ./struct.English.html
+pub fn set<M: Messages + 'static>(messages: M) {
+    CURRENT.with(|c| *c.borrow_mut() = Rc::new(messages));
+}
+
+/// Restores replaced catalog when dropped (even on panic).
+struct Restore(Rc<dyn Messages>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CURRENT.with(|c| mem::swap(&mut *c.borrow_mut(), &mut self.0));
+    }
+}
+
+/// Runs `f` with `messages` as the current catalog, the previous one is
+/// restored afterwards.
+///
+/// # Examples
+///
+/// ```
+/// use std::rc::Rc;
+/// use fui::messages::{self, Messages, Table};
+///
+/// let table = Table::from_json(r#"{"form.cancel": "Anuluj"}"#).unwrap();
+/// let table: Rc<Messages> = Rc::new(table);
+/// assert_eq!(messages::with(&table, || messages::get("form.cancel")), "Anuluj");
+/// assert_eq!(messages::get("form.cancel"), "Cancel");
+/// ```
+pub fn with<R, F: FnOnce() -> R>(messages: &Rc<dyn Messages>, f: F) -> R {
+    let previous = CURRENT.with(|c| mem::replace(&mut *c.borrow_mut(), messages.clone()));
+    let _restore = Restore(previous);
+    f()
+}
+
+/// Returns the current catalog.
+pub(crate) fn current() -> Rc<dyn Messages> {
+    CURRENT.with(|c| c.borrow().clone())
+}
+
+/// Runs `f` with `messages` as the current catalog if they're set.
+pub(crate) fn with_some<R, F: FnOnce() -> R>(messages: &Option<Rc<dyn Messages>>, f: F) -> R {
+    match *messages {
+        Some(ref messages) => with(messages, f),
+        None => f(),
+    }
+}
+
+/// Returns text of message with `key` without parameters.
+pub fn get(key: &str) -> String {
+    Message::new(key).to_string()
+}
+
+/// Checks if message with `key` is in the current catalog (or in [English]).
+///
+/// [English]: ./struct.English.html
+pub fn is_known(key: &str) -> bool {
+    CURRENT.with(|c| c.borrow().template(key)).is_some() || English.template(key).is_some()
+}
+
+fn template(key: &str) -> String {
+    CURRENT
+        .with(|c| c.borrow().template(key))
+        .or_else(|| English.template(key))
+        .unwrap_or(key.to_string())
+}
+
+/// Default catalog of messages.
+#[derive(Clone, Debug)]
+pub struct English;
+
+impl Messages for English {
+    fn template(&self, key: &str) -> Option<String> {
+        let text = match key {
+            "form.cancel" => "Cancel",
+            "form.submit" => "Submit (Ctrl+f)",
+            "form.warnings" => "Submit anyway?",
+            "form.warning" => "{flag}: {error}",
+            "form.submit_anyway" => "Submit anyway",
+            "fui.pick_action" => "Pick action",
+            "fui.form_invalid" => "Form invalid!",
+            "fui.clipboard_failed" => "Copying to clipboard - FAILED.\nFix form errors first.",
+            "dialog.cancel" => "Cancel",
+            "cli.invalid_value" => "Invalid value for '{flag}': {error}",
+            "cli.more_info" => "For more information try --help",
+            "cli.warning" => "warning: Suspicious value for '{flag}': {error}",
+            "cli.strict" => "Treat warnings as errors",
+            "check.duplicate_label" => "Label {label} is used by more than one field",
+            "check.invalid_label" => {
+                "Label {label} can't be a CLI option (it must be non-empty, without spaces, not starting with -)"
+            }
+            "check.reserved_label" => "Label {label} clashes with built-in option --{label}",
+            "check.invalid_initial" => "Initial value {value} is invalid: {error}",
+            "check.required_checkbox" => "Checkbox has always a value, so Required has no effect",
+            "check.invalid_action" => {
+                "Action {name} can't be a CLI subcommand (it must be non-empty, without spaces, not starting with -)"
+            }
+            "editor.failed" => "Editor failed!",
+            "editor.empty" => "<empty> (Enter: edit)",
+            "editor.line" => "{line} (Enter: edit)",
+            "editor.lines" => "{line} (+{more} lines, Enter: edit)",
+            "editor.not_set" => "Editor is not set",
+            "editor.create_failed" => "Can't create temporary file in {path}: {error}",
+            "editor.run_failed" => "Can't run {program}: {error}",
+            "editor.exit_status" => "{program} exited with {status}",
+            "editor.read_failed" => "Can't read {path}: {error}",
+            "editor.unsupported_backend" => "External editor can't be run with {backend} backend",
+            "path.browse" => "Browse",
+            "path.select" => "Select",
+            "path_browser.hints" => {
+                "→ expand, ← collapse, Backspace parent dir, . hidden files, Enter select"
+            }
+            "path_browser.hints_multiple" => {
+                "→ expand, ← collapse, Backspace parent dir, . hidden files, Space mark, Enter select"
+            }
+            "feeders.command_failed" => "Command {command} failed: {error}",
+            "feeders.command_timeout" => "Command {command} timed out after {timeout}",
+            "feeders.file_failed" => "Can't read {path}: {error}",
+            "feeders.file_invalid" => "File {path} is invalid: {error}",
+            "subform.add_record" => "Add record",
+            "subform.invalid_json" => "Invalid JSON: {error}",
+            "subform.not_object" => "Value must be a JSON object",
+            "subform.not_array" => "Value must be an array of JSON objects",
+            "subform.field_error" => "{label}: {error}",
+            "subform.record_error" => "#{record} {label}: {error}",
+            "checkbox.not_bool" => "Value can't be converterd to bool",
+            "toggle.invalid" => "Value must be one of: \"\", \"true\", \"false\"",
+            "keyvalue.not_pair" => "Value {value} must be in format KEY=VALUE",
+            "keyvalue.key_error" => "Key {key}: {error}",
+            "keyvalue.value_error" => "Value of {key}: {error}",
+            "converters.home_unknown" => "Home dir is unknown",
+            "converters.var_missing" => "Variable {name} is not set",
+            "converters.cwd_unknown" => "Current dir is unknown: {error}",
+            "converters.unresolved_path" => "Can't resolve path {value}: {error}",
+            "converters.not_integer" => "Value {value} is not an integer",
+            "converters.not_number" => "Value {value} is not a number",
+            "converters.not_bool" => "Value {value} is not a boolean",
+            "converters.not_duration" => "Value {value} is not a duration (like 1d2h3m4s)",
+            "validators.required" => "Field is required",
+            "validators.required.describe" => "required",
+            "validators.path_free" => "Path is already used",
+            "validators.path_free.describe" => "path not used yet",
+            "validators.not_dir" => "It's not a dir",
+            "validators.dir_missing" => "Dir doesn't exist",
+            "validators.dir_exists.describe" => "existing dir",
+            "validators.not_file" => "It's not a file",
+            "validators.file_missing" => "File doesn't exist",
+            "validators.file_exists.describe" => "existing file",
+            "validators.io_error" => "Can't check {value}: {error}",
+            "validators.not_readable" => "Path {value} is not readable",
+            "validators.readable.describe" => "readable path",
+            "validators.not_writable" => "Path {value} is not writable",
+            "validators.writable.describe" => "writable path",
+            "validators.not_executable" => "Path {value} is not an executable file",
+            "validators.executable.describe" => "executable file",
+            "validators.parent_dir_missing" => "Parent dir of {value} doesn't exist",
+            "validators.parent_not_writable" => "Parent dir of {value} is not writable",
+            "validators.parent_dir_writable.describe" => "path in writable dir",
+            "validators.outside_dir" => "Path {value} is outside of {dir}",
+            "validators.inside_dir.describe" => "path inside {dir}",
+            "validators.extension" => "Value {value} must have extension: {extensions}",
+            "validators.extension.describe" => "extension: {extensions}",
+            "validators.glob_match" => "Value {value} does not match: {pattern}",
+            "validators.glob_match.describe" => "matching {pattern}",
+            "validators.max_file_size" => "File must have at most {max} bytes",
+            "validators.max_file_size.describe" => "file of at most {max} bytes",
+            "validators.not_symlink" => "Path {value} is not a symlink",
+            "validators.symlink.describe" => "symlink",
+            "validators.symlink" => "Path {value} is a symlink",
+            "validators.not_symlink.describe" => "not a symlink",
+            "validators.one_of" => "Value must be one of options",
+            "validators.one_of.describe" => "one of: {options}",
+            "validators.regex" => "Value {value} does not match: \"{pattern}\" regular exp.",
+            "validators.regex.describe" => "matching {pattern}",
+            "validators.any_of.separator" => " or ",
+            "validators.all_of.separator" => "; ",
+            "validators.all_of.describe_separator" => ", ",
+            "validators.not" => "Value {value} is not allowed",
+            "validators.not.describe" => "not {constraint}",
+            "validators.optional.describe" => "optional, {constraint}",
+            "validators.when.describe" => "{constraint} (conditionally)",
+            "validators.min_length" => "Value must have at least {min} characters",
+            "validators.min_length.describe" => "at least {min} characters",
+            "validators.max_length" => "Value must have at most {max} characters",
+            "validators.max_length.describe" => "at most {max} characters",
+            "validators.range" => "Value must be {constraint}",
+            "validators.range.between" => "between {min} and {max}",
+            "validators.range.at_least" => "at least {min}",
+            "validators.range.at_most" => "at most {max}",
+            "validators.range.any" => "any number",
+            "validators.not_number" => "Value {value} is not a number",
+            "validators.float.describe" => "number",
+            "validators.integer" => "Value {value} is not an integer",
+            "validators.integer.describe" => "integer",
+            "validators.url" => "Value {value} is not a URL",
+            "validators.url.describe" => "URL",
+            "validators.email" => "Value {value} is not an e-mail",
+            "validators.email.describe" => "e-mail",
+            "validators.ip_addr" => "Value {value} is not an IP address",
+            "validators.ip_addr.describe" => "IP address",
+            "validators.socket_addr" => "Value {value} is not a socket address (IP:PORT)",
+            "validators.socket_addr.describe" => "socket address (IP:PORT)",
+            "validators.port" => "Value {value} is not a port (1-65535)",
+            "validators.port.describe" => "port (1-65535)",
+            "validators.semver" => "Value {value} is not a semantic version",
+            "validators.semver.describe" => "semantic version (MAJOR.MINOR.PATCH)",
+            "validators.hostname" => "Value {value} is not a host name",
+            "validators.hostname.describe" => "host name",
+            "validators.json" => "Value is not a JSON: {error}",
+            "validators.json.describe" => "JSON",
+            "validators.glob" => "Value {value} is not a glob pattern: {error}",
+            "validators.glob.describe" => "glob pattern",
+            _ => return None,
+        };
+        Some(text.to_string())
+    }
+}
+
+/// Catalog of messages loaded from key-template pairs.
+///
+/// # Examples
+///
+/// ```
+/// use fui::messages::{Messages, Table};
+///
+/// let table = Table::from_json(r#"{"form.cancel": "Anuluj"}"#).unwrap();
+/// assert_eq!(table.template("form.cancel"), Some("Anuluj".to_string()));
+/// assert_eq!(table.template("form.submit"), None);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Table {
+    templates: HashMap<String, String>,
+}
+
+impl Table {
+    /// Creates a new `Table` from `templates` (key -> template).
+    pub fn new(templates: HashMap<String, String>) -> Self {
+        Table { templates }
+    }
+
+    /// Creates a new `Table` from JSON object (key -> template).
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let value: Value = serde_json::from_str(json).map_err(|e| format!("{}", e))?;
+        let map = match value {
+            Value::Object(map) => map,
+            _ => return Err("Messages must be a JSON object".to_string()),
+        };
+        let mut templates = HashMap::with_capacity(map.len());
+        for (key, template) in map.into_iter() {
+            match template {
+                Value::String(t) => {
+                    templates.insert(key, t);
+                }
+                _ => return Err(format!("Message {:?} must be a string", key)),
+            }
+        }
+        Ok(Table::new(templates))
+    }
+}
+
+impl Messages for Table {
+    fn template(&self, key: &str) -> Option<String> {
+        self.templates.get(key).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_message_is_not_looked_up() {
+        assert_eq!(Message::raw("form.cancel").to_string(), "form.cancel");
+        assert_eq!(Message::new("form.cancel").to_string(), "Cancel");
+    }
+
+    #[test]
+    fn message_is_rendered_with_params() {
+        let msg = Message::new("validators.min_length").param("min", 3);
+        assert_eq!(msg.to_string(), "Value must have at least 3 characters");
+    }
+
+    #[test]
+    fn unknown_key_is_rendered_as_is() {
+        assert_eq!(get("Some text"), "Some text");
+    }
+
+    #[test]
+    fn current_catalog_falls_back_to_english() {
+        set(Table::from_json(r#"{"validators.required": "Pole jest wymagane"}"#).unwrap());
+        assert_eq!(get("validators.required"), "Pole jest wymagane");
+        assert_eq!(get("form.cancel"), "Cancel");
+        set(English);
+        assert_eq!(get("validators.required"), "Field is required");
+    }
+
+    #[test]
+    fn table_requires_string_templates() {
+        assert!(Table::from_json("[]").is_err());
+        assert_eq!(
+            Table::from_json(r#"{"a": 1}"#).unwrap_err(),
+            "Message \"a\" must be a string"
+        );
+    }
+}
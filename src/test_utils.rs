@@ -0,0 +1,63 @@
+//! Helpers shared by tests.
+use std::env;
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Unique temporary dir removed (with its content) when dropped,
+/// so failing tests don't leave it behind.
+pub(crate) struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Creates an empty dir with `name` in its name.
+    pub(crate) fn new(name: &str) -> Self {
+        let idx = COUNTER.fetch_add(1, Ordering::SeqCst);
+        let dir_name = format!("fui-{}-{}-{}", name, process::id(), idx);
+        let path = env::temp_dir().join(dir_name);
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TempDir { path }
+    }
+
+    /// Creates dirs of `paths` (relative to this dir).
+    pub(crate) fn dirs(self, paths: &[&str]) -> Self {
+        for path in paths {
+            fs::create_dir_all(self.path.join(path)).unwrap();
+        }
+        self
+    }
+
+    /// Writes `content` to file `path` (relative to this dir), creating its parent dirs.
+    pub(crate) fn file(self, path: &str, content: &str) -> Self {
+        let path = self.path.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+        self
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
@@ -1,16 +1,312 @@
 //! Provides data validators used by `Fields`.
+use glob;
+#[cfg(unix)]
+use libc;
+use messages;
+use messages::Message;
 use regex::Regex;
+use serde_json;
+use serde_json::map::Map;
+use serde_json::value::Value;
 use std::any::Any;
+use std::env;
+use std::fmt;
 use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::net;
 use std::ops::Deref;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
+
+/// Severity of [ValidationError].
+///
+/// [ValidationError]: ./struct.ValidationError.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Severity {
+    /// Data is invalid, it can't be submitted.
+    Error,
+    /// Data is suspicious, it's submitted after user's confirmation (see [Warn]).
+    ///
+    /// [Warn]: ./struct.Warn.html
+    Warning,
+}
+
+/// Error of validation with `code` telling its kind.
+///
+/// Errors of built-in validators (and converters) have codes equal to keys
+/// of their messages (like `validators.required`), errors given as plain text
+/// have code `custom`. It converts to `String` (its message) for
+/// compatibility with text errors.
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{MinLength, ValidationError, Validator};
+///
+/// let e = MinLength(3).error("ab").unwrap();
+/// assert_eq!(e.code, "validators.min_length");
+/// assert_eq!(e.message, "Value must have at least 3 characters");
+/// assert_eq!(e.params, vec![("min".to_string(), "3".to_string())]);
+///
+/// let e = ValidationError::from("Something is wrong").field("name");
+/// assert_eq!(e.code, "custom");
+/// assert_eq!(e.field, Some("name".to_string()));
+/// assert_eq!(String::from(e), "Something is wrong");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationError {
+    /// Kind of error (like `validators.required`).
+    pub code: String,
+    /// Text of error shown to user.
+    pub message: String,
+    /// Parameters of the message (like `min` of `MinLength`).
+    pub params: Vec<(String, String)>,
+    /// Label of field which is invalid (it's set by form).
+    pub field: Option<String>,
+    /// Path to invalid value nested in field's value, like `["0", "host"]`
+    /// for field `host` of the first record of a repeatable sub-form.
+    pub path: Box<[String]>,
+    /// Tells if it's an error or only a warning.
+    pub severity: Severity,
+}
+
+impl ValidationError {
+    /// Creates a new `ValidationError` with `code` and `message`.
+    pub fn new<C: Into<String>, M: Into<String>>(code: C, message: M) -> Self {
+        ValidationError {
+            code: code.into(),
+            message: message.into(),
+            params: Vec::new(),
+            field: None,
+            path: Box::new([]),
+            severity: Severity::Error,
+        }
+    }
+
+    /// Makes this error a warning.
+    pub fn warning(mut self) -> Self {
+        self.severity = Severity::Warning;
+        self
+    }
+
+    /// Checks if this error is only a warning.
+    pub fn is_warning(&self) -> bool {
+        self.severity == Severity::Warning
+    }
+
+    /// Sets label of invalid field.
+    pub fn field<IS: Into<String>>(mut self, label: IS) -> Self {
+        self.field = Some(label.into());
+        self
+    }
+
+    /// Prepends `segment` (a label or an index) to path of invalid nested value.
+    pub fn nested_in<IS: Into<String>>(mut self, segment: IS) -> Self {
+        let mut path = self.path.into_vec();
+        path.insert(0, segment.into());
+        self.path = path.into_boxed_slice();
+        self
+    }
+
+    /// Returns JSON object with `code`, `message`, `params`, `field`, `path` & `severity` of this error.
+    pub fn to_json(&self) -> Value {
+        let params = self
+            .params
+            .iter()
+            .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+            .collect::<Map<String, Value>>();
+        let mut map = Map::with_capacity(6);
+        map.insert("code".to_string(), Value::String(self.code.clone()));
+        map.insert("message".to_string(), Value::String(self.message.clone()));
+        map.insert("params".to_string(), Value::Object(params));
+        let field = match self.field {
+            Some(ref f) => Value::String(f.clone()),
+            None => Value::Null,
+        };
+        map.insert("field".to_string(), field);
+        let path = self.path.iter().cloned().map(Value::String).collect();
+        map.insert("path".to_string(), Value::Array(path));
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        map.insert("severity".to_string(), Value::String(severity.to_string()));
+        Value::Object(map)
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl From<Message> for ValidationError {
+    fn from(msg: Message) -> Self {
+        let code = if !msg.is_raw() && messages::is_known(&msg.key) {
+            msg.key.clone()
+        } else {
+            CUSTOM.to_string()
+        };
+        ValidationError {
+            code,
+            message: msg.to_string(),
+            params: msg.params,
+            field: None,
+            path: Box::new([]),
+            severity: Severity::Error,
+        }
+    }
+}
+
+impl From<String> for ValidationError {
+    fn from(message: String) -> Self {
+        ValidationError::new(CUSTOM, message)
+    }
+}
+
+impl<'a> From<&'a str> for ValidationError {
+    fn from(message: &'a str) -> Self {
+        ValidationError::new(CUSTOM, message)
+    }
+}
+
+impl From<ValidationError> for String {
+    fn from(error: ValidationError) -> Self {
+        error.message
+    }
+}
+
+impl PartialEq<String> for ValidationError {
+    fn eq(&self, other: &String) -> bool {
+        &self.message == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for ValidationError {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.message == *other
+    }
+}
+
+/// Code of errors given as plain text.
+const CUSTOM: &str = "custom";
 
 /// Adds behaviour of validation.
-pub trait Validator: Debug {
+///
+/// Validators implement `validate`, its text is shown as is. Validators which
+/// want their errors translated by the current catalog (see [messages]) also
+/// implement `message`, like built-in ones do.
+///
+/// [messages]: ../messages/index.html
+pub trait Validator {
     /// Validates data returning None (when Ok) or String with error.
     fn validate(&self, data: &str) -> Option<String>;
+    /// Validates data returning None (when Ok) or `Message` with error.
+    fn message(&self, data: &str) -> Option<Message> {
+        self.validate(data).map(Message::raw)
+    }
+    /// Validates data returning None (when Ok) or `ValidationError`.
+    fn error(&self, data: &str) -> Option<ValidationError> {
+        self.message(data).map(ValidationError::from)
+    }
     /// Allows downcasting `self` to a `Any`.
     fn as_any(&self) -> &Any;
+    /// Describes constraint of this validator (e.g. for help message).
+    fn describe(&self) -> Option<String> {
+        None
+    }
+}
+
+impl Debug for dyn Validator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.describe() {
+            Some(desc) => write!(f, "Validator({:?})", desc),
+            None => write!(f, "Validator"),
+        }
+    }
+}
+
+/// Any function returning `Err` with message (or `ValidationError`) for
+/// invalid data is a validator.
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{ValidationError, Validator};
+///
+/// let v = |data: &str| {
+///     if data.ends_with(".tar") {
+///         Ok(())
+///     } else {
+///         Err("Archive must be a .tar file".to_string())
+///     }
+/// };
+/// assert_eq!(v.validate("a.tar"), None);
+/// assert_eq!(v.validate("a.zip"), Some("Archive must be a .tar file".to_string()));
+///
+/// let v = |_: &str| Err(ValidationError::new("archive.format", "Unknown format"));
+/// assert_eq!(v.error("a.zip").unwrap().code, "archive.format");
+/// ```
+impl<F, E> Validator for F
+where
+    F: Fn(&str) -> Result<(), E> + 'static,
+    E: Into<ValidationError>,
+{
+    fn validate(&self, data: &str) -> Option<String> {
+        self.error(data).map(String::from)
+    }
+
+    fn error(&self, data: &str) -> Option<ValidationError> {
+        self(data).err().map(Into::into)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Adds behaviour of validation which depends on values of other fields.
+pub trait ContextValidator {
+    /// Validates `data` knowing `values` of other (valid) fields of the form.
+    ///
+    /// Returns None (when Ok) or `ValidationError`.
+    fn validate(&self, data: &str, values: &Map<String, Value>) -> Option<ValidationError>;
+}
+
+/// Any function returning `Err` with message (or `ValidationError`) for
+/// invalid data is a context validator.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate serde_json;
+/// # extern crate fui;
+/// use fui::validators::ContextValidator;
+/// use fui::Value;
+///
+/// # fn main() {
+/// let v = |data: &str, values: &serde_json::Map<String, Value>| {
+///     if values.get("source") == Some(&json!(data)) {
+///         Err("Target must differ from source".to_string())
+///     } else {
+///         Ok(())
+///     }
+/// };
+/// let values = json!({"source": "./a"});
+/// let values = values.as_object().unwrap();
+/// assert_eq!(v.validate("./b", values), None);
+/// assert_eq!(v.validate("./a", values).unwrap(), "Target must differ from source");
+/// # }
+/// ```
+impl<F, E> ContextValidator for F
+where
+    F: Fn(&str, &Map<String, Value>) -> Result<(), E>,
+    E: Into<ValidationError>,
+{
+    fn validate(&self, data: &str, values: &Map<String, Value>) -> Option<ValidationError> {
+        self(data, values).err().map(Into::into)
+    }
 }
 
 /// Ensures data is included.
@@ -29,8 +325,12 @@ pub struct Required;
 
 impl Validator for Required {
     fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
         if data.len() == 0 {
-            Some("Field is required".to_string())
+            Some(Message::new("validators.required"))
         } else {
             None
         }
@@ -39,6 +339,10 @@ impl Validator for Required {
     fn as_any(&self) -> &Any {
         self
     }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.required.describe"))
+    }
 }
 
 /// Ensures path is free.
@@ -62,9 +366,13 @@ pub struct PathFree;
 
 impl Validator for PathFree {
     fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
         let path = Path::new(data);
         if path.exists() {
-            Some("Path is already used".to_string())
+            Some(Message::new("validators.path_free"))
         } else {
             None
         }
@@ -73,6 +381,10 @@ impl Validator for PathFree {
     fn as_any(&self) -> &Any {
         self
     }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.path_free.describe"))
+    }
 }
 
 /// Ensures data is dir path which exists.
@@ -96,21 +408,27 @@ pub struct DirExists;
 
 impl Validator for DirExists {
     fn validate(&self, data: &str) -> Option<String> {
-        let path = Path::new(data);
-        if path.exists() {
-            if path.metadata().unwrap().is_dir() {
-                None
-            } else {
-                Some("It's not a dir".to_string())
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        match Path::new(data).metadata() {
+            Ok(ref meta) if meta.is_dir() => None,
+            Ok(_) => Some(Message::new("validators.not_dir")),
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => {
+                Some(Message::new("validators.dir_missing"))
             }
-        } else {
-            Some("Dir doesn't exist".to_string())
+            Err(e) => Some(io_error(data, e)),
         }
     }
 
     fn as_any(&self) -> &Any {
         self
     }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.dir_exists.describe"))
+    }
 }
 
 /// Ensures data is file path which exists.
@@ -134,68 +452,1811 @@ pub struct FileExists;
 
 impl Validator for FileExists {
     fn validate(&self, data: &str) -> Option<String> {
-        let path = Path::new(data);
-        if path.exists() {
-            if path.metadata().unwrap().is_file() {
-                None
-            } else {
-                Some("It's not a file".to_string())
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        match Path::new(data).metadata() {
+            Ok(ref meta) if meta.is_file() => None,
+            Ok(_) => Some(Message::new("validators.not_file")),
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => {
+                Some(Message::new("validators.file_missing"))
             }
+            Err(e) => Some(io_error(data, e)),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.file_exists.describe"))
+    }
+}
+
+/// Ensures data is path which can be read (file can be opened, dir can be listed).
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{Readable, Validator};
+///
+/// assert_eq!(Readable.validate("./Cargo.toml"), None);
+/// assert_eq!(Readable.validate("./src"), None);
+/// assert!(Readable.validate("./missing-file").unwrap().starts_with("Can't check \"./missing-file\""));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Readable;
+
+impl Validator for Readable {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        let path = Path::new(data);
+        let meta = match path.metadata() {
+            Ok(meta) => meta,
+            Err(e) => return Some(io_error(data, e)),
+        };
+        let readable = if meta.is_dir() {
+            fs::read_dir(path).is_ok()
+        } else {
+            fs::File::open(path).is_ok()
+        };
+        if readable {
+            None
         } else {
-            Some("File doesn't exist".to_string())
+            Some(with_value("validators.not_readable", data))
         }
     }
 
-    fn as_any(&self) -> &Any {
+    fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.readable.describe"))
+    }
 }
 
-/// Ensures value is one of provided options.
+/// Ensures data is path which can be written.
+///
+/// It's checked by `access(2)` on Unix (without writing anything), by
+/// read-only attribute elsewhere.
 ///
 /// # Examples
 ///
 /// ```
-/// use fui::validators::OneOf;
-/// use fui::validators::Validator;
+/// use fui::validators::{Validator, Writable};
 ///
-/// let v = OneOf(vec!["a", "b"]);
-/// assert_eq!(v.validate("a"), None);
-/// assert_eq!(v.validate("xxx"), Some("Value must be one of options".to_string()));
+/// assert_eq!(Writable.validate("./src"), None);
+/// assert!(Writable.validate("./missing-dir/file").is_some());
 /// ```
 #[derive(Clone, Debug)]
-pub struct OneOf<T>(pub T);
+pub struct Writable;
 
-impl<T: Debug + 'static> Validator for OneOf<Vec<T>>
-where
-    T: Deref<Target = str>,
-{
+impl Validator for Writable {
     fn validate(&self, data: &str) -> Option<String> {
-        if let None = self.0.iter().position(|x| &**x == data) {
-            Some("Value must be one of options".to_string())
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        let path = Path::new(data);
+        let meta = match path.metadata() {
+            Ok(meta) => meta,
+            Err(e) => return Some(io_error(data, e)),
+        };
+        if is_writable(path, &meta) {
+            None
         } else {
+            Some(with_value("validators.not_writable", data))
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.writable.describe"))
+    }
+}
+
+/// Ensures data is file which can be executed.
+///
+/// On Unix execute permission bits are checked, elsewhere any file passes.
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{Executable, Validator};
+///
+/// assert_eq!(
+///     Executable.validate("./src"),
+///     Some("Path \"./src\" is not an executable file".to_string())
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct Executable;
+
+impl Validator for Executable {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        let meta = match Path::new(data).metadata() {
+            Ok(meta) => meta,
+            Err(e) => return Some(io_error(data, e)),
+        };
+        if meta.is_file() && is_executable(&meta) {
             None
+        } else {
+            Some(with_value("validators.not_executable", data))
         }
     }
 
-    fn as_any(&self) -> &Any {
+    fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.executable.describe"))
+    }
 }
 
-impl Validator for Regex {
+#[cfg(unix)]
+fn is_executable(meta: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    meta.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_: &fs::Metadata) -> bool {
+    true
+}
+
+/// Checks if user can write to `path`.
+#[cfg(unix)]
+fn is_writable(path: &Path, _meta: &fs::Metadata) -> bool {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+    match CString::new(path.as_os_str().as_bytes()) {
+        Ok(path) => unsafe { libc::access(path.as_ptr(), libc::W_OK) == 0 },
+        Err(_) => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_writable(_path: &Path, meta: &fs::Metadata) -> bool {
+    !meta.permissions().readonly()
+}
+
+/// Ensures parent dir of data exists and is writable.
+///
+/// It's the check for output files, which don't have to exist yet. Parent
+/// dir is checked like by [Writable].
+///
+/// [Writable]: ./struct.Writable.html
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{ParentDirWritable, Validator};
+///
+/// assert_eq!(ParentDirWritable.validate("./src/new-file.rs"), None);
+/// assert_eq!(ParentDirWritable.validate("new-file.rs"), None);
+/// assert_eq!(
+///     ParentDirWritable.validate("./missing-dir/file"),
+///     Some("Parent dir of \"./missing-dir/file\" doesn't exist".to_string())
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct ParentDirWritable;
+
+impl Validator for ParentDirWritable {
     fn validate(&self, data: &str) -> Option<String> {
-        if self.is_match(data) {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        let parent = match Path::new(data).parent() {
+            Some(p) if p != Path::new("") => p,
+            _ => Path::new("."),
+        };
+        match parent.metadata() {
+            Ok(ref meta) if meta.is_dir() => {
+                if is_writable(parent, meta) {
+                    None
+                } else {
+                    Some(with_value("validators.parent_not_writable", data))
+                }
+            }
+            Ok(_) => Some(with_value("validators.parent_dir_missing", data)),
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => {
+                Some(with_value("validators.parent_dir_missing", data))
+            }
+            Err(e) => Some(io_error(data, e)),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.parent_dir_writable.describe"))
+    }
+}
+
+/// Ensures data is path inside of dir (or the dir itself).
+///
+/// Relative paths are resolved against the current dir, `..` and (for
+/// existing paths) symlinks are resolved before comparing.
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{InsideDir, Validator};
+///
+/// let v = InsideDir::new("./src");
+/// assert_eq!(v.validate("./src/lib.rs"), None);
+/// assert_eq!(v.validate("./src/new/file.rs"), None);
+/// assert_eq!(
+///     v.validate("./src/../Cargo.toml"),
+///     Some("Path \"./src/../Cargo.toml\" is outside of \"./src\"".to_string())
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct InsideDir(pub PathBuf);
+
+impl InsideDir {
+    /// Creates a new `InsideDir` accepting paths inside of `dir`.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        InsideDir(dir.into())
+    }
+}
+
+impl Validator for InsideDir {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        let (dir, path) = match (resolve(&self.0), resolve(Path::new(data))) {
+            (Ok(dir), Ok(path)) => (dir, path),
+            (Err(e), _) | (_, Err(e)) => return Some(io_error(data, e)),
+        };
+        if path.starts_with(&dir) {
             None
         } else {
-            Some(format!(
-                "Value {:?} does not match: \"{:?}\" regular exp.",
-                data, self
-            ))
+            let msg =
+                with_value("validators.outside_dir", data).param("dir", format!("{:?}", self.0));
+            Some(msg)
         }
     }
 
     fn as_any(&self) -> &Any {
         self
     }
+
+    fn describe(&self) -> Option<String> {
+        let msg = Message::new("validators.inside_dir.describe").param("dir", self.0.display());
+        Some(msg.to_string())
+    }
+}
+
+/// Makes `path` absolute with `..` & symlinks (of its existing part) resolved.
+fn resolve(path: &Path) -> io::Result<PathBuf> {
+    let path = env::current_dir()?.join(path);
+    let mut resolved = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::CurDir => {}
+            c => resolved.push(c.as_os_str()),
+        }
+    }
+    let mut existing = resolved.as_path();
+    let mut rest = Vec::new();
+    while !existing.exists() {
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name.to_owned());
+                existing = parent;
+            }
+            _ => return Ok(resolved.clone()),
+        }
+    }
+    let mut canonical = existing.canonicalize()?;
+    for name in rest.iter().rev() {
+        canonical.push(name);
+    }
+    Ok(canonical)
+}
+
+/// Ensures data is path with one of extensions (compared case-insensitively).
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{Extension, Validator};
+///
+/// let v = Extension(vec!["yml", ".yaml"]);
+/// assert_eq!(v.validate("config.YAML"), None);
+/// assert_eq!(
+///     v.validate("config.json"),
+///     Some("Value \"config.json\" must have extension: yml, yaml".to_string())
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct Extension<T>(pub T);
+
+impl<T> Extension<Vec<T>>
+where
+    T: Deref<Target = str>,
+{
+    fn extensions(&self) -> Vec<&str> {
+        self.0.iter().map(|x| x.trim_start_matches('.')).collect()
+    }
+}
+
+impl<T: Debug + 'static> Validator for Extension<Vec<T>>
+where
+    T: Deref<Target = str>,
+{
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        let extensions = self.extensions();
+        let found = Path::new(data)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| extensions.iter().any(|x| x.eq_ignore_ascii_case(e)))
+            .unwrap_or(false);
+        if found {
+            None
+        } else {
+            let msg =
+                with_value("validators.extension", data).param("extensions", extensions.join(", "));
+            Some(msg)
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        let msg = Message::new("validators.extension.describe")
+            .param("extensions", self.extensions().join(", "));
+        Some(msg.to_string())
+    }
+}
+
+/// Ensures data matches glob pattern.
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+/// extern crate glob;
+///
+/// use fui::validators::Validator;
+/// use glob::Pattern;
+///
+/// # fn main() {
+/// let v = Pattern::new("**/*.rs").unwrap();
+/// assert_eq!(v.validate("src/lib.rs"), None);
+/// assert_eq!(
+///     v.validate("Cargo.toml"),
+///     Some("Value \"Cargo.toml\" does not match: **/*.rs".to_string())
+/// );
+/// # }
+/// ```
+impl Validator for glob::Pattern {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        if self.matches(data) {
+            None
+        } else {
+            let msg = with_value("validators.glob_match", data).param("pattern", self);
+            Some(msg)
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        let msg = Message::new("validators.glob_match.describe").param("pattern", self);
+        Some(msg.to_string())
+    }
+}
+
+/// Ensures data is file with size at most given number of bytes.
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{MaxFileSize, Validator};
+///
+/// assert_eq!(MaxFileSize(1024 * 1024).validate("./Cargo.toml"), None);
+/// assert_eq!(
+///     MaxFileSize(1).validate("./Cargo.toml"),
+///     Some("File must have at most 1 bytes".to_string())
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct MaxFileSize(pub u64);
+
+impl Validator for MaxFileSize {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        match Path::new(data).metadata() {
+            Ok(ref meta) if !meta.is_file() => Some(Message::new("validators.not_file")),
+            Ok(ref meta) if meta.len() > self.0 => {
+                Some(Message::new("validators.max_file_size").param("max", self.0))
+            }
+            Ok(_) => None,
+            Err(e) => Some(io_error(data, e)),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        let msg = Message::new("validators.max_file_size.describe").param("max", self.0);
+        Some(msg.to_string())
+    }
+}
+
+/// Ensures data is path of symbolic link.
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{Symlink, Validator};
+///
+/// assert_eq!(
+///     Symlink.validate("./Cargo.toml"),
+///     Some("Path \"./Cargo.toml\" is not a symlink".to_string())
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct Symlink;
+
+impl Validator for Symlink {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        match fs::symlink_metadata(data) {
+            Ok(ref meta) if meta.file_type().is_symlink() => None,
+            Ok(_) => Some(with_value("validators.not_symlink", data)),
+            Err(e) => Some(io_error(data, e)),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.symlink.describe"))
+    }
+}
+
+/// Ensures data is path which isn't a symbolic link (or doesn't exist).
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{NotSymlink, Validator};
+///
+/// assert_eq!(NotSymlink.validate("./Cargo.toml"), None);
+/// assert_eq!(NotSymlink.validate("./missing-file"), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct NotSymlink;
+
+impl Validator for NotSymlink {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        match fs::symlink_metadata(data) {
+            Ok(ref meta) if meta.file_type().is_symlink() => {
+                Some(with_value("validators.symlink", data))
+            }
+            Ok(_) => None,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => Some(io_error(data, e)),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.not_symlink.describe"))
+    }
+}
+
+/// Ensures value is one of provided options.
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::OneOf;
+/// use fui::validators::Validator;
+///
+/// let v = OneOf(vec!["a", "b"]);
+/// assert_eq!(v.validate("a"), None);
+/// assert_eq!(v.validate("xxx"), Some("Value must be one of options".to_string()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct OneOf<T>(pub T);
+
+impl<T: Debug + 'static> Validator for OneOf<Vec<T>>
+where
+    T: Deref<Target = str>,
+{
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        if let None = self.0.iter().position(|x| &**x == data) {
+            Some(Message::new("validators.one_of"))
+        } else {
+            None
+        }
+    }
+
+    fn as_any(&self) -> &Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        let options = self.0.iter().map(|x| &**x).collect::<Vec<&str>>();
+        let msg = Message::new("validators.one_of.describe").param("options", options.join(", "));
+        Some(msg.to_string())
+    }
+}
+
+impl Validator for Regex {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        if self.is_match(data) {
+            None
+        } else {
+            let msg = Message::new("validators.regex")
+                .param("value", format!("{:?}", data))
+                .param("pattern", format!("{:?}", self));
+            Some(msg)
+        }
+    }
+
+    fn as_any(&self) -> &Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        let msg = Message::new("validators.regex.describe")
+            .param("pattern", format!("{:?}", self.as_str()));
+        Some(msg.to_string())
+    }
+}
+
+/// Ensures at least one of `validators` passes.
+///
+/// When all fail, their errors are combined into one.
+///
+/// # Examples
+///
+/// ```
+/// extern crate fui;
+/// extern crate regex;
+///
+/// use fui::validators::{AnyOf, OneOf, Validator};
+/// use regex::Regex;
+///
+/// # fn main() {
+/// let v = AnyOf(vec![
+///     Box::new(OneOf(vec!["latest"])),
+///     Box::new(Regex::new(r"^v\d+\.\d+\.\d+$").unwrap()),
+/// ]);
+/// assert_eq!(v.validate("latest"), None);
+/// assert_eq!(v.validate("v1.0.2"), None);
+/// let error = v.validate("1.0").unwrap();
+/// assert!(error.starts_with("Value must be one of options or Value \"1.0\" does not match"));
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AnyOf(pub Vec<Box<dyn Validator>>);
+
+/// Combines `errors` of inner validators into one, joined by separator `key`.
+///
+/// A single error is kept (with its code), combined ones are a warning only
+/// if all of them are warnings.
+fn combine_errors(mut errors: Vec<ValidationError>, key: &str) -> Option<ValidationError> {
+    if errors.len() < 2 {
+        return errors.pop();
+    }
+    let all_warnings = errors.iter().all(|e| e.is_warning());
+    let text = errors
+        .iter()
+        .map(|e| e.message.as_str())
+        .collect::<Vec<&str>>()
+        .join(&messages::get(key));
+    let error = ValidationError::from(Message::raw(text));
+    if all_warnings {
+        Some(error.warning())
+    } else {
+        Some(error)
+    }
+}
+
+impl Validator for AnyOf {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.error(data).map(|e| e.message)
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        self.error(data).map(|e| Message::raw(e.message))
+    }
+
+    fn error(&self, data: &str) -> Option<ValidationError> {
+        let mut errors = Vec::with_capacity(self.0.len());
+        for v in self.0.iter() {
+            match v.error(data) {
+                Some(e) => errors.push(e),
+                None => return None,
+            }
+        }
+        combine_errors(errors, "validators.any_of.separator")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        let found = self
+            .0
+            .iter()
+            .filter_map(|v| v.describe())
+            .collect::<Vec<String>>();
+        if !found.is_empty() {
+            Some(found.join(&messages::get("validators.any_of.separator")))
+        } else {
+            None
+        }
+    }
+}
+
+/// Ensures all of `validators` pass.
+///
+/// It's useful inside other combinators, errors are combined into one.
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{AllOf, PathFree, Required, Validator};
+///
+/// let v = AllOf(vec![Box::new(Required), Box::new(PathFree)]);
+/// assert_eq!(v.validate("./free-path"), None);
+/// assert_eq!(v.validate("./src"), Some("Path is already used".to_string()));
+/// assert_eq!(v.validate(""), Some("Field is required".to_string()));
+/// ```
+#[derive(Debug)]
+pub struct AllOf(pub Vec<Box<dyn Validator>>);
+
+impl Validator for AllOf {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.error(data).map(|e| e.message)
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        self.error(data).map(|e| Message::raw(e.message))
+    }
+
+    fn error(&self, data: &str) -> Option<ValidationError> {
+        let errors = self
+            .0
+            .iter()
+            .filter_map(|v| v.error(data))
+            .collect::<Vec<ValidationError>>();
+        combine_errors(errors, "validators.all_of.separator")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        let found = self
+            .0
+            .iter()
+            .filter_map(|v| v.describe())
+            .collect::<Vec<String>>();
+        if !found.is_empty() {
+            Some(found.join(&messages::get("validators.all_of.describe_separator")))
+        } else {
+            None
+        }
+    }
+}
+
+/// Ensures `validator` fails.
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{Not, OneOf, Validator};
+///
+/// let v = Not(OneOf(vec!["root", "admin"]));
+/// assert_eq!(v.validate("user"), None);
+/// assert_eq!(v.validate("root"), Some("Value \"root\" is not allowed".to_string()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Not<V>(pub V);
+
+impl<V: Validator + 'static> Validator for Not<V> {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        match self.0.error(data) {
+            Some(_) => None,
+            None => Some(with_value("validators.not", data)),
+        }
+    }
+
+    fn error(&self, data: &str) -> Option<ValidationError> {
+        self.message(data).map(ValidationError::from)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        let msg = Message::new("validators.not.describe");
+        self.0
+            .describe()
+            .map(|x| msg.param("constraint", x).to_string())
+    }
+}
+
+/// Runs `validator` only if data is not empty.
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{FileExists, Optional, Validator};
+///
+/// let v = Optional(FileExists);
+/// assert_eq!(v.validate(""), None);
+/// assert_eq!(v.validate("./Cargo.toml"), None);
+/// assert_eq!(v.validate("./missing-file"), Some("File doesn't exist".to_string()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Optional<V>(pub V);
+
+impl<V: Validator + 'static> Validator for Optional<V> {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        if data.is_empty() {
+            None
+        } else {
+            self.0.message(data)
+        }
+    }
+
+    fn error(&self, data: &str) -> Option<ValidationError> {
+        if data.is_empty() {
+            None
+        } else {
+            self.0.error(data)
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        let msg = Message::new("validators.optional.describe");
+        self.0
+            .describe()
+            .map(|x| msg.param("constraint", x).to_string())
+    }
+}
+
+/// Runs `validator` only if `predicate` is true for data.
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{FileExists, Validator, When};
+///
+/// let v = When(|data: &str| !data.starts_with("http"), FileExists);
+/// assert_eq!(v.validate("https://example.com/Cargo.toml"), None);
+/// assert_eq!(v.validate("./Cargo.toml"), None);
+/// assert_eq!(v.validate("./missing-file"), Some("File doesn't exist".to_string()));
+/// ```
+#[derive(Clone)]
+pub struct When<P, V>(pub P, pub V);
+
+impl<P, V: Debug> Debug for When<P, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "When(<predicate>, {:?})", self.1)
+    }
+}
+
+impl<P, V> Validator for When<P, V>
+where
+    P: Fn(&str) -> bool + 'static,
+    V: Validator + 'static,
+{
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        if (self.0)(data) {
+            self.1.message(data)
+        } else {
+            None
+        }
+    }
+
+    fn error(&self, data: &str) -> Option<ValidationError> {
+        if (self.0)(data) {
+            self.1.error(data)
+        } else {
+            None
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        let msg = Message::new("validators.when.describe");
+        self.1
+            .describe()
+            .map(|x| msg.param("constraint", x).to_string())
+    }
+}
+
+/// Makes errors of `validator` warnings, which don't block submitting.
+///
+/// Form asks user to confirm warnings, CLI prints them (unless `--strict`
+/// is given, then they're errors).
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{PathFree, Validator, Warn};
+///
+/// let v = Warn(PathFree);
+/// assert_eq!(v.error("./free-path"), None);
+/// assert!(v.error("./src").unwrap().is_warning());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Warn<V>(pub V);
+
+impl<V: Validator + 'static> Validator for Warn<V> {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        self.0.message(data)
+    }
+
+    fn error(&self, data: &str) -> Option<ValidationError> {
+        self.0.error(data).map(|e| e.warning())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        self.0.describe()
+    }
+}
+
+/// Ensures data has at least `n` characters.
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{MinLength, Validator};
+///
+/// assert_eq!(MinLength(3).validate("abc"), None);
+/// assert_eq!(
+///     MinLength(3).validate("ab"),
+///     Some("Value must have at least 3 characters".to_string())
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct MinLength(pub usize);
+
+impl Validator for MinLength {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        if data.chars().count() < self.0 {
+            Some(Message::new("validators.min_length").param("min", self.0))
+        } else {
+            None
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        let msg = Message::new("validators.min_length.describe").param("min", self.0);
+        Some(msg.to_string())
+    }
+}
+
+/// Ensures data has at most `n` characters.
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{MaxLength, Validator};
+///
+/// assert_eq!(MaxLength(3).validate("abc"), None);
+/// assert_eq!(
+///     MaxLength(3).validate("abcd"),
+///     Some("Value must have at most 3 characters".to_string())
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct MaxLength(pub usize);
+
+impl Validator for MaxLength {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        if data.chars().count() > self.0 {
+            Some(Message::new("validators.max_length").param("max", self.0))
+        } else {
+            None
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        let msg = Message::new("validators.max_length.describe").param("max", self.0);
+        Some(msg.to_string())
+    }
+}
+
+/// Ensures data is a number in range (bounds are inclusive).
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{Range, Validator};
+///
+/// let v = Range::new(1, 10);
+/// assert_eq!(v.validate("10"), None);
+/// assert_eq!(v.validate("0.5"), Some("Value must be between 1 and 10".to_string()));
+/// assert_eq!(v.validate("x"), Some("Value \"x\" is not a number".to_string()));
+/// assert_eq!(Range::at_least(0).validate("-1"), Some("Value must be at least 0".to_string()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Range {
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl Range {
+    /// Creates a new `Range` between `min` and `max`.
+    pub fn new<N: Into<f64>>(min: N, max: N) -> Self {
+        Range {
+            min: Some(min.into()),
+            max: Some(max.into()),
+        }
+    }
+    /// Creates a new `Range` without upper bound.
+    pub fn at_least<N: Into<f64>>(min: N) -> Self {
+        Range {
+            min: Some(min.into()),
+            max: None,
+        }
+    }
+    /// Creates a new `Range` without lower bound.
+    pub fn at_most<N: Into<f64>>(max: N) -> Self {
+        Range {
+            min: None,
+            max: Some(max.into()),
+        }
+    }
+}
+
+impl Validator for Range {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        let number = match data.parse::<f64>() {
+            Ok(n) if !n.is_nan() => n,
+            _ => return Some(not_number(data)),
+        };
+        let too_low = self.min.map(|min| number < min).unwrap_or(false);
+        let too_high = self.max.map(|max| number > max).unwrap_or(false);
+        if too_low || too_high {
+            Some(Message::new("validators.range").param("constraint", self.describe().unwrap()))
+        } else {
+            None
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        let desc = match (self.min, self.max) {
+            (Some(min), Some(max)) => Message::new("validators.range.between")
+                .param("min", min)
+                .param("max", max),
+            (Some(min), None) => Message::new("validators.range.at_least").param("min", min),
+            (None, Some(max)) => Message::new("validators.range.at_most").param("max", max),
+            (None, None) => Message::new("validators.range.any"),
+        };
+        Some(desc.to_string())
+    }
+}
+
+/// Ensures data is an integer number.
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{Integer, Validator};
+///
+/// assert_eq!(Integer.validate("-12"), None);
+/// assert_eq!(Integer.validate("1.5"), Some("Value \"1.5\" is not an integer".to_string()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Integer;
+
+impl Validator for Integer {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        match data.parse::<i64>() {
+            Ok(_) => None,
+            Err(_) => Some(with_value("validators.integer", data)),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.integer.describe"))
+    }
+}
+
+/// Ensures data is a (finite) float number.
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{Float, Validator};
+///
+/// assert_eq!(Float.validate("1.5e3"), None);
+/// assert_eq!(Float.validate("inf"), Some("Value \"inf\" is not a number".to_string()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Float;
+
+impl Validator for Float {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        match data.parse::<f64>() {
+            Ok(n) if n.is_finite() => None,
+            _ => Some(not_number(data)),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.float.describe"))
+    }
+}
+
+/// Ensures data is URL with scheme and host (like `https://example.com/path`).
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{Url, Validator};
+///
+/// assert_eq!(Url.validate("https://example.com:8080/a?b=c#d"), None);
+/// assert_eq!(Url.validate("example.com"), Some("Value \"example.com\" is not a URL".to_string()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Url;
+
+thread_local! {
+    static URL: Regex =
+        Regex::new(r"^[A-Za-z][A-Za-z0-9+.-]*://[^\s/?#@]+(@[^\s/?#@]+)?([/?#][^\s]*)?$").unwrap();
+}
+
+impl Validator for Url {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        if URL.with(|re| re.is_match(data)) {
+            None
+        } else {
+            Some(with_value("validators.url", data))
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.url.describe"))
+    }
+}
+
+/// Ensures data looks like an e-mail address.
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{Email, Validator};
+///
+/// assert_eq!(Email.validate("user@example.com"), None);
+/// assert_eq!(Email.validate("user@"), Some("Value \"user@\" is not an e-mail".to_string()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Email;
+
+impl Validator for Email {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        let valid = match data.rfind('@') {
+            Some(idx) => {
+                let (user, host) = (&data[..idx], &data[idx + 1..]);
+                !user.is_empty()
+                    && !user.contains(char::is_whitespace)
+                    && host.contains('.')
+                    && Hostname.validate(host).is_none()
+            }
+            None => false,
+        };
+        if valid {
+            None
+        } else {
+            Some(with_value("validators.email", data))
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.email.describe"))
+    }
+}
+
+/// Ensures data is IP address (v4 or v6).
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{IpAddr, Validator};
+///
+/// assert_eq!(IpAddr.validate("127.0.0.1"), None);
+/// assert_eq!(IpAddr.validate("::1"), None);
+/// assert_eq!(IpAddr.validate("256.0.0.1"), Some("Value \"256.0.0.1\" is not an IP address".to_string()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct IpAddr;
+
+impl Validator for IpAddr {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        match data.parse::<net::IpAddr>() {
+            Ok(_) => None,
+            Err(_) => Some(with_value("validators.ip_addr", data)),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.ip_addr.describe"))
+    }
+}
+
+/// Ensures data is socket address, IP address with port (like `127.0.0.1:8080`).
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{SocketAddr, Validator};
+///
+/// assert_eq!(SocketAddr.validate("127.0.0.1:8080"), None);
+/// assert_eq!(SocketAddr.validate("[::1]:80"), None);
+/// assert_eq!(
+///     SocketAddr.validate("127.0.0.1"),
+///     Some("Value \"127.0.0.1\" is not a socket address (IP:PORT)".to_string())
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct SocketAddr;
+
+impl Validator for SocketAddr {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        match data.parse::<net::SocketAddr>() {
+            Ok(_) => None,
+            Err(_) => Some(with_value("validators.socket_addr", data)),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.socket_addr.describe"))
+    }
+}
+
+/// Ensures data is port number (1-65535).
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{Port, Validator};
+///
+/// assert_eq!(Port.validate("8080"), None);
+/// assert_eq!(Port.validate("0"), Some("Value \"0\" is not a port (1-65535)".to_string()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Port;
+
+impl Validator for Port {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        match data.parse::<u16>() {
+            Ok(n) if n > 0 => None,
+            _ => Some(with_value("validators.port", data)),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.port.describe"))
+    }
+}
+
+/// Ensures data is a semantic version (like `1.2.3-rc.1+build`).
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{SemVer, Validator};
+///
+/// assert_eq!(SemVer.validate("1.2.3-rc.1+build.5"), None);
+/// assert_eq!(SemVer.validate("1.2"), Some("Value \"1.2\" is not a semantic version".to_string()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SemVer;
+
+thread_local! {
+    static SEMVER: Regex = Regex::new(concat!(
+        r"^(0|[1-9]\d*)\.(0|[1-9]\d*)\.(0|[1-9]\d*)",
+        r"(-[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?",
+        r"(\+[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?$"
+    ))
+    .unwrap();
+}
+
+impl Validator for SemVer {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        if SEMVER.with(|re| re.is_match(data)) {
+            None
+        } else {
+            Some(with_value("validators.semver", data))
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.semver.describe"))
+    }
+}
+
+/// Ensures data is a valid host name (RFC 1123).
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{Hostname, Validator};
+///
+/// assert_eq!(Hostname.validate("my-host.example.com"), None);
+/// assert_eq!(Hostname.validate("-host"), Some("Value \"-host\" is not a host name".to_string()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Hostname;
+
+impl Validator for Hostname {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        let name = data.trim_end_matches('.');
+        let valid = !name.is_empty()
+            && name.len() <= 253
+            && name.split('.').all(|label| {
+                !label.is_empty()
+                    && label.len() <= 63
+                    && !label.starts_with('-')
+                    && !label.ends_with('-')
+                    && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            });
+        if valid {
+            None
+        } else {
+            Some(with_value("validators.hostname", data))
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.hostname.describe"))
+    }
+}
+
+/// Ensures data is a valid JSON.
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{Json, Validator};
+///
+/// assert_eq!(Json.validate(r#"{"a": [1, 2]}"#), None);
+/// assert!(Json.validate("{a: 1}").unwrap().starts_with("Value is not a JSON: "));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Json;
+
+impl Validator for Json {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        match serde_json::from_str::<serde_json::Value>(data) {
+            Ok(_) => None,
+            Err(e) => Some(Message::new("validators.json").param("error", e)),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.json.describe"))
+    }
+}
+
+/// Ensures data is a valid glob pattern (like `src/**/*.rs`).
+///
+/// # Examples
+///
+/// ```
+/// use fui::validators::{Glob, Validator};
+///
+/// assert_eq!(Glob.validate("src/**/*.rs"), None);
+/// assert!(Glob.validate("src/[a").unwrap().starts_with("Value \"src/[a\" is not a glob pattern"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Glob;
+
+impl Validator for Glob {
+    fn validate(&self, data: &str) -> Option<String> {
+        self.message(data).map(|m| m.to_string())
+    }
+
+    fn message(&self, data: &str) -> Option<Message> {
+        match glob::Pattern::new(data) {
+            Ok(_) => None,
+            Err(e) => Some(with_value("validators.glob", data).param("error", e.msg)),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn describe(&self) -> Option<String> {
+        Some(messages::get("validators.glob.describe"))
+    }
+}
+
+/// Message with `key` and `data` as parameter `value`.
+fn with_value(key: &str, data: &str) -> Message {
+    Message::new(key).param("value", format!("{:?}", data))
+}
+
+/// Message about failed I/O `error` while checking `data`.
+fn io_error(data: &str, error: io::Error) -> Message {
+    with_value("validators.io_error", data).param("error", error)
+}
+
+fn not_number(data: &str) -> Message {
+    with_value("validators.not_number", data)
+}
+
+/// Checks if `validator` makes data required.
+///
+/// It's true for `Required` and combinators which always need it.
+pub(crate) fn is_required(validator: &dyn Validator) -> bool {
+    let any = validator.as_any();
+    if any.downcast_ref::<Required>().is_some() {
+        true
+    } else if let Some(all) = any.downcast_ref::<AllOf>() {
+        all.0.iter().any(|v| is_required(&**v))
+    } else if let Some(one) = any.downcast_ref::<AnyOf>() {
+        !one.0.is_empty() && one.0.iter().all(|v| is_required(&**v))
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_utils::TempDir;
+
+    #[test]
+    fn required_is_found_in_combinators() {
+        assert!(is_required(&Required));
+        assert!(is_required(&AllOf(vec![
+            Box::new(PathFree),
+            Box::new(Required)
+        ])));
+        assert!(is_required(&AnyOf(vec![
+            Box::new(Required),
+            Box::new(AllOf(vec![Box::new(Required)])),
+        ])));
+    }
+
+    #[test]
+    fn built_in_errors_are_message_keys() {
+        assert_eq!(
+            Required.message(""),
+            Some(Message::new("validators.required"))
+        );
+        assert_eq!(
+            MinLength(2).message("a"),
+            Some(Message::new("validators.min_length").param("min", 2))
+        );
+        let v = Optional(Port);
+        assert_eq!(v.message("0").unwrap().key, "validators.port");
+    }
+
+    #[test]
+    fn errors_are_translated_by_current_catalog() {
+        let table = r#"{"validators.min_length": "Minimum {min} znaki"}"#;
+        messages::set(messages::Table::from_json(table).unwrap());
+        assert_eq!(
+            MinLength(3).validate(""),
+            Some("Minimum 3 znaki".to_string())
+        );
+        messages::set(messages::English);
+    }
+
+    #[derive(Debug)]
+    struct NotAdmin;
+
+    impl Validator for NotAdmin {
+        fn validate(&self, data: &str) -> Option<String> {
+            if data == "admin" {
+                Some("Name is taken".to_string())
+            } else {
+                None
+            }
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    #[test]
+    fn custom_validator_implements_only_validate() {
+        assert_eq!(NotAdmin.validate("root"), None);
+        assert_eq!(
+            NotAdmin.message("admin"),
+            Some(Message::raw("Name is taken"))
+        );
+        assert_eq!(NotAdmin.error("admin").unwrap().code, "custom");
+    }
+
+    #[derive(Debug)]
+    struct NotCancel;
+
+    impl Validator for NotCancel {
+        fn validate(&self, _: &str) -> Option<String> {
+            Some("form.cancel".to_string())
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    #[test]
+    fn text_of_custom_validator_is_not_translated() {
+        let e = NotCancel.error("x").unwrap();
+        assert_eq!(e.code, "custom");
+        assert_eq!(e.message, "form.cancel");
+    }
+
+    #[test]
+    fn unknown_message_key_is_custom_error() {
+        let e = ValidationError::from(Message::new("Some text"));
+        assert_eq!(e.code, "custom");
+        assert_eq!(e.message, "Some text");
+    }
+
+    #[test]
+    fn error_is_dumped_to_json() {
+        let e = Port.error("0").unwrap().field("port");
+        let exp = json!({
+            "code": "validators.port",
+            "message": "Value \"0\" is not a port (1-65535)",
+            "params": {"value": "\"0\""},
+            "field": "port",
+            "path": [],
+            "severity": "error",
+        });
+        assert_eq!(e.to_json(), exp);
+    }
+
+    #[test]
+    fn constraints_are_described() {
+        assert_eq!(
+            Required.describe(),
+            Some(messages::get("validators.required.describe"))
+        );
+        assert_eq!(
+            Range::new(1, 5).describe(),
+            Some("between 1 and 5".to_string())
+        );
+        assert_eq!(
+            Range::at_most(0.5).describe(),
+            Some("at most 0.5".to_string())
+        );
+        let v = AllOf(vec![Box::new(Required), Box::new(Optional(Port))]);
+        assert_eq!(
+            v.describe(),
+            Some("required, optional, port (1-65535)".to_string())
+        );
+        let v = AnyOf(vec![Box::new(IpAddr), Box::new(Hostname)]);
+        assert_eq!(v.describe(), Some("IP address or host name".to_string()));
+    }
+
+    #[test]
+    fn combinators_keep_code_of_single_error() {
+        let v = AllOf(vec![Box::new(Required), Box::new(Optional(Port))]);
+        assert_eq!(v.error("").unwrap().code, "validators.required");
+        assert_eq!(v.error("x").unwrap().code, "validators.port");
+        let v = AnyOf(vec![Box::new(IpAddr), Box::new(Hostname)]);
+        let error = v.error("a..b").unwrap();
+        assert_eq!(error.code, CUSTOM);
+        assert!(error.message.contains(" or "));
+        let v = AnyOf(vec![Box::new(Required)]);
+        assert_eq!(v.error("").unwrap().code, "validators.required");
+        assert_eq!(Not(Required).error("x").unwrap().code, "validators.not");
+    }
+
+    #[test]
+    fn combinators_keep_severity_of_warnings() {
+        let v = AllOf(vec![Box::new(Warn(Required)), Box::new(Optional(Port))]);
+        assert!(v.error("").unwrap().is_warning());
+        assert!(!v.error("x").unwrap().is_warning());
+        let v = AllOf(vec![Box::new(Warn(Required)), Box::new(Warn(Port))]);
+        let error = v.error("").unwrap();
+        assert!(error.is_warning());
+        assert_eq!(error.code, CUSTOM);
+        let v = AllOf(vec![Box::new(Warn(Required)), Box::new(Port)]);
+        assert!(!v.error("").unwrap().is_warning());
+        let v = AnyOf(vec![Box::new(Warn(Required)), Box::new(Warn(Port))]);
+        assert!(v.error("").unwrap().is_warning());
+    }
+
+    #[test]
+    fn hostname_rejects_invalid_labels() {
+        assert_eq!(Hostname.validate("localhost"), None);
+        assert_eq!(Hostname.validate("example.com."), None);
+        assert!(Hostname.validate("").is_some());
+        assert!(Hostname.validate("a..b").is_some());
+        assert!(Hostname.validate("under_score.com").is_some());
+        assert!(Hostname.validate(&"a".repeat(64)).is_some());
+    }
+
+    #[test]
+    fn url_needs_scheme_and_host() {
+        assert_eq!(Url.validate("http://localhost?q=1"), None);
+        assert_eq!(Url.validate("ssh://user@host/repo.git"), None);
+        assert!(Url.validate("http://").is_some());
+        assert!(Url.validate("http://a b").is_some());
+    }
+
+    #[test]
+    fn fs_validators_report_io_errors() {
+        let missing = "./missing-dir/file";
+        let validators: [&dyn Validator; 5] =
+            [&Readable, &Writable, &Executable, &MaxFileSize(1), &Symlink];
+        for v in validators.iter() {
+            assert_eq!(v.message(missing).unwrap().key, "validators.io_error");
+        }
+        assert_eq!(
+            DirExists.validate(""),
+            Some("Dir doesn't exist".to_string())
+        );
+    }
+
+    #[test]
+    fn writable_checks_dont_write_anything() {
+        let dir = TempDir::new("writable").file("file", "content");
+        let modified = fs::metadata(&*dir).unwrap().modified().unwrap();
+        assert_eq!(Writable.validate(dir.to_str().unwrap()), None);
+        assert_eq!(Writable.validate(dir.join("file").to_str().unwrap()), None);
+        assert_eq!(
+            ParentDirWritable.validate(dir.join("new").to_str().unwrap()),
+            None
+        );
+        assert_eq!(fs::read_dir(&*dir).unwrap().count(), 1);
+        assert_eq!(fs::metadata(&*dir).unwrap().modified().unwrap(), modified);
+        assert_eq!(fs::read_to_string(dir.join("file")).unwrap(), "content");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn executable_checks_permission_bits() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = TempDir::new("executable").file("script", "");
+        let path = dir.join("script");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        let data = path.to_str().unwrap();
+        assert_eq!(
+            Executable.message(data).unwrap().key,
+            "validators.not_executable"
+        );
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        assert_eq!(Executable.validate(data), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_are_detected() {
+        let dir = TempDir::new("symlinks");
+        let link = dir.join("link");
+        ::std::os::unix::fs::symlink("/missing-target", &link).unwrap();
+        let link = link.to_str().unwrap();
+        assert_eq!(Symlink.validate(link), None);
+        assert_eq!(NotSymlink.message(link).unwrap().key, "validators.symlink");
+    }
+
+    #[test]
+    fn inside_dir_resolves_relative_paths() {
+        let v = InsideDir::new(env::current_dir().unwrap());
+        assert_eq!(v.validate("src/../Cargo.toml"), None);
+        assert_eq!(v.validate("."), None);
+        assert!(v.validate("..").is_some());
+        assert!(v.validate("/").is_some());
+    }
+
+    #[test]
+    fn required_is_not_found_when_skippable() {
+        assert!(!is_required(&PathFree));
+        assert!(!is_required(&AnyOf(vec![
+            Box::new(Required),
+            Box::new(PathFree)
+        ])));
+        assert!(!is_required(&AnyOf(vec![])));
+        assert!(!is_required(&Optional(Required)));
+        assert!(!is_required(&Not(Required)));
+        assert!(!is_required(&Warn(Required)));
+    }
 }
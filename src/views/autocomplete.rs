@@ -1,8 +1,10 @@
 use std::rc::Rc;
 
 use cursive::event::{Callback, Event, EventResult, Key};
+use cursive::theme::{BaseColor, Color, Effect};
 use cursive::traits::Boxable;
 use cursive::traits::View;
+use cursive::utils::markup::StyledString;
 use cursive::view::SizeConstraint;
 use cursive::view::ViewWrapper;
 use cursive::views::BoxView;
@@ -11,19 +13,70 @@ use cursive::Cursive;
 use cursive::With;
 
 use super::is_value_from_select;
-use feeders::Feeder;
+use feeders::{Cursor, Feeder, Suggestion, Total};
 
 // TODO: better performance while typing
 
 pub type OnSubmit = Option<Rc<Fn(&mut Cursive, Rc<String>)>>;
 
+/// Makes label of `item` with chars at `positions` highlighted.
+fn highlighted(item: &str, positions: &[usize]) -> StyledString {
+    let mut label = StyledString::new();
+    for (idx, c) in item.chars().enumerate() {
+        if positions.contains(&idx) {
+            label.append_styled(c.to_string(), Effect::Underline);
+        } else {
+            label.append_plain(c.to_string());
+        }
+    }
+    label
+}
+
+/// Replaces suggestions in `select` with `items` highlighting matches of `text`.
+///
+/// Descriptions of suggestions are shown dimmed after their display text.
+///
+/// Returns `true` if feeder's error is shown instead of suggestions.
+fn show_suggestions(
+    select: &mut SelectView,
+    feeder: &dyn Feeder,
+    text: &str,
+    items: Vec<Suggestion>,
+) -> bool {
+    select.clear();
+    if items.is_empty() {
+        if let Some(error) = feeder.error(text) {
+            let label = StyledString::styled(error, Color::Dark(BaseColor::Red));
+            select.add_item(label, String::new());
+            return true;
+        }
+    }
+    for item in items {
+        let mut label = highlighted(&item.display, &feeder.highlight(text, &item.display));
+        if !item.description.is_empty() {
+            label.append_styled(
+                format!(" — {}", item.description),
+                Color::Light(BaseColor::Black),
+            );
+        }
+        select.add_item(label, item.value);
+    }
+    false
+}
+
 /// Single selection view with suggestions.
 pub struct Autocomplete {
     view: BoxView<LinearLayout>,
 
     feeder: Rc<Feeder>,
+    // Suggestions for typed text, read while scrolling
+    cursor: Cursor,
+    loaded: Vec<Suggestion>,
+    exhausted: bool,
     shown_count: u8,
     submit_anything: bool,
+    // Select view shows feeder's error (it can't be selected)
+    error_shown: bool,
     suggestion_offset: usize,
     // User typed text handled manually (EditView content is changing by selection)
     typed_value: Rc<String>,
@@ -36,25 +89,27 @@ impl Autocomplete {
     pub fn new<T: Feeder>(feeder: T) -> Autocomplete {
         let shown_count = 5;
 
-        let select =
-            SelectView::<String>::new().with_all_str(feeder.query("", 0, shown_count).into_iter());
-
         let layout = LinearLayout::vertical()
             .child(EditView::new())
-            .child(select)
+            .child(SelectView::<String>::new())
             .fixed_height(shown_count + 1);
 
-        let ac = Autocomplete {
+        let mut ac = Autocomplete {
             view: layout,
 
             feeder: Rc::new(feeder),
+            cursor: Cursor::empty(),
+            loaded: Vec::new(),
+            exhausted: false,
             shown_count: shown_count as u8,
             submit_anything: false,
+            error_shown: false,
             suggestion_offset: 0usize,
             typed_value: Rc::new("".to_string()),
 
             on_submit: None,
         };
+        ac.refresh_listing();
 
         ac
     }
@@ -72,24 +127,58 @@ impl Autocomplete {
 
     /// Sets text value.
     pub fn value(mut self, initial: &str) -> Self {
-        self.get_edit_view_mut().set_content(initial);
-        self.refresh_listing();
+        self.set_value(initial);
         self
     }
 
+    /// Sets text value.
+    ///
+    /// Non-chainable variant.
+    pub fn set_value(&mut self, value: &str) {
+        self.get_edit_view_mut().set_content(value);
+        self.refresh_listing();
+    }
+
+    /// Queries feeder again (like after its data changed).
+    pub fn refresh(&mut self) {
+        self.refresh_listing();
+    }
+
     /// Refresh suggestions.
     fn refresh_listing(&mut self) {
-        let feeder = Rc::clone(&self.feeder);
         let text = self.get_edit_view().get_content();
+        self.cursor = Cursor::open(&self.feeder, &text);
+        self.typed_value = text;
+        self.loaded.clear();
+        self.exhausted = false;
+        self.suggestion_offset = 0;
         let shown_count = self.shown_count as usize;
-        let select = self.get_select_view_mut();
-        select.clear();
-        select.add_all_str((*feeder).query(text.as_ref(), 0, shown_count).into_iter());
+        self.load_until(shown_count);
+        self.show_loaded();
+    }
+
+    /// Returns total count of suggestions for typed text.
+    pub fn total(&self) -> Total {
+        if self.exhausted {
+            Total::Exact(self.loaded.len())
+        } else {
+            self.cursor.total()
+        }
+    }
+
+    /// Reads suggestions from cursor until `count` of them are loaded.
+    fn load_until(&mut self, count: usize) {
+        while self.loaded.len() < count && !self.exhausted {
+            match self.cursor.next() {
+                Some(item) => self.loaded.push(item),
+                None => self.exhausted = true,
+            }
+        }
     }
 
     /// Copy selected text to edit view.
     fn selection_to_edit(&mut self) {
-        if !self.get_select_view().is_empty() {
+        if !self.get_select_view().is_empty() && !self.error_shown {
             if let Some(x) = self.get_select_view_mut().selection() {
                 self.get_edit_view_mut().set_content((&*x).clone());
             }
@@ -98,6 +187,9 @@ impl Autocomplete {
 
     /// Checks if value comes from suggestions.
     pub fn is_value_from_select(&self, to_check: &str) -> bool {
+        if self.error_shown {
+            return false;
+        }
         let select = self.get_select_view();
         is_value_from_select(select, to_check)
     }
@@ -151,19 +243,28 @@ impl Autocomplete {
             .unwrap()
     }
 
+    /// Shows next page of suggestions (from `suggestion_offset`).
+    ///
+    /// Returns `false` if there aren't enough suggestions to fill it.
     fn load_data(&mut self) -> bool {
-        let shown_count = self.shown_count as usize;
-        let feeder = Rc::clone(&self.feeder);
-        let typed_value = &*self.typed_value.clone();
-        let data = (*feeder).query(typed_value, self.suggestion_offset, shown_count);
-        if data.len() == shown_count {
-            let select = self.get_select_view_mut();
-            select.clear();
-            select.add_all_str(data.into_iter());
-            true
-        } else {
-            false
+        let end = self.suggestion_offset + self.shown_count as usize;
+        self.load_until(end);
+        if self.loaded.len() < end {
+            return false;
         }
+        self.show_loaded();
+        true
+    }
+
+    /// Shows loaded suggestions from `suggestion_offset`.
+    fn show_loaded(&mut self) {
+        let start = self.suggestion_offset.min(self.loaded.len());
+        let end = (start + self.shown_count as usize).min(self.loaded.len());
+        let data = self.loaded[start..end].to_vec();
+        let feeder = Rc::clone(&self.feeder);
+        let typed_value = Rc::clone(&self.typed_value);
+        self.error_shown =
+            show_suggestions(self.get_select_view_mut(), &*feeder, &typed_value, data);
     }
 
     fn is_nth(&mut self, nth: usize) -> bool {
@@ -331,3 +432,82 @@ impl ViewWrapper for Autocomplete {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    struct Counted {
+        queries: Rc<Cell<usize>>,
+    }
+
+    impl Feeder for Counted {
+        fn query(&self, _: &str, position: usize, items_count: usize) -> Vec<String> {
+            self.queries.set(self.queries.get() + 1);
+            (position..30.min(position + items_count))
+                .map(|x| x.to_string())
+                .collect()
+        }
+    }
+
+    fn shown(ac: &Autocomplete) -> Vec<String> {
+        ac.get_select_view()
+            .iter()
+            .map(|(_, v)| v.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn scrolling_reads_feeder_by_pages() {
+        let queries = Rc::new(Cell::new(0));
+        let mut ac = Autocomplete::new(Counted {
+            queries: Rc::clone(&queries),
+        });
+        assert_eq!(shown(&ac), vec!["0", "1", "2", "3", "4"]);
+        for _ in 0..30 {
+            ac.scroll_down();
+        }
+        assert_eq!(shown(&ac), vec!["25", "26", "27", "28", "29"]);
+        assert_eq!(*ac.get_value(), "29");
+        // second page is shorter than requested, so it's the last one
+        assert_eq!(queries.get(), 2);
+        assert_eq!(ac.total(), Total::Exact(30));
+    }
+
+    #[test]
+    fn feeder_error_is_shown_and_cant_be_selected() {
+        struct Failing;
+        impl Feeder for Failing {
+            fn query(&self, _: &str, _: usize, _: usize) -> Vec<String> {
+                Vec::new()
+            }
+            fn error(&self, _: &str) -> Option<String> {
+                Some("failed".to_string())
+            }
+        }
+        let mut ac = Autocomplete::new(Failing);
+        assert_eq!(ac.get_select_view().len(), 1);
+        ac.scroll_down();
+        assert_eq!(*ac.get_value(), "");
+        assert!(!ac.is_value_from_select(""));
+    }
+
+    #[test]
+    fn suggestion_shows_description_and_inserts_value() {
+        use feeders::Items;
+        let mut ac = Autocomplete::new(Items::from_suggestions(vec![
+            Suggestion::new("gzip").description("fast, moderate ratio"),
+            Suggestion::new("xz").display("xz (lzma)"),
+        ]));
+        let labels = ac
+            .get_select_view()
+            .iter()
+            .map(|(l, _)| l.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(labels, vec!["gzip — fast, moderate ratio", "xz (lzma)"]);
+        assert_eq!(shown(&ac), vec!["gzip", "xz"]);
+        ac.scroll_down();
+        assert_eq!(*ac.get_value(), "xz");
+    }
+}
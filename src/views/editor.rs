@@ -0,0 +1,378 @@
+use std::cell::RefCell;
+use std::env;
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use cursive::event::{Event, EventResult, Key};
+use cursive::vec::Vec2;
+use cursive::view::{View, ViewWrapper};
+use cursive::views::{Dialog, TextView};
+use cursive::Cursive;
+
+use messages;
+use messages::Message;
+
+static NEXT_FILE: AtomicUsize = AtomicUsize::new(0);
+
+/// Summary of (possibly long) text which is edited in external editor.
+///
+/// * `Enter` or `Ctrl+e` opens `$VISUAL` (or `$EDITOR`, `vi` by default)
+///
+/// Terminal is handed over to editor for its time and screen is repainted
+/// after editor exits. It's supported by `ncurses` & `pancurses` backends,
+/// other ones show an error instead of opening editor.
+pub struct Editor {
+    view: TextView,
+    value: Rc<RefCell<String>>,
+    extension: String,
+}
+
+impl Editor {
+    /// Creates a new `Editor` with `value`.
+    pub fn new<IS: Into<String>>(value: IS) -> Self {
+        let mut editor = Editor {
+            view: TextView::new(""),
+            value: Rc::new(RefCell::new(value.into())),
+            extension: "".to_string(),
+        };
+        editor.refresh();
+        editor
+    }
+
+    /// Sets extension of edited file (e.g. `yaml`), so editor can highlight syntax.
+    pub fn extension<IS: Into<String>>(mut self, extension: IS) -> Self {
+        self.extension = extension.into();
+        self
+    }
+
+    /// Returns edited value.
+    pub fn get_value(&self) -> String {
+        self.value.borrow().clone()
+    }
+
+    /// Sets edited value.
+    pub fn set_value<IS: Into<String>>(&mut self, value: IS) {
+        *self.value.borrow_mut() = value.into();
+        self.refresh();
+    }
+
+    fn refresh(&mut self) {
+        let summary = summarize(&self.value.borrow());
+        self.view.set_content(summary);
+    }
+
+    fn request_edition(&self) -> EventResult {
+        let value = Rc::clone(&self.value);
+        let extension = self.extension.clone();
+        // callback runs after event is handled, so catalog of form is kept for it
+        let catalog = messages::current();
+        EventResult::with_cb(move |c| {
+            messages::with(&catalog, || edit_in_place(c, &value, &extension))
+        })
+    }
+}
+
+/// Edits `value` in external editor, error is shown in a dialog.
+fn edit_in_place(siv: &mut Cursive, value: &RefCell<String>, extension: &str) {
+    let result = terminal::suspend().and_then(|()| {
+        let current = value.borrow().clone();
+        let result = edit(&current, extension);
+        terminal::resume();
+        result
+    });
+    // editor painted over the screen, clearing forces full repaint
+    siv.clear();
+    match result {
+        Ok(edited) => *value.borrow_mut() = edited,
+        Err(e) => siv.add_layer(Dialog::info(e).title(messages::get("editor.failed"))),
+    }
+}
+
+/// Shows first line of `value` and count of the rest.
+fn summarize(value: &str) -> String {
+    let lines = value.lines().collect::<Vec<&str>>();
+    let msg = match lines.len() {
+        0 => Message::new("editor.empty"),
+        1 => Message::new("editor.line").param("line", lines[0]),
+        n => Message::new("editor.lines")
+            .param("line", lines[0])
+            .param("more", n - 1),
+    };
+    msg.to_string()
+}
+
+impl ViewWrapper for Editor {
+    wrap_impl!(self.view: TextView);
+
+    fn wrap_layout(&mut self, size: Vec2) {
+        // value could be changed by editor, while this view wasn't touched
+        self.refresh();
+        self.view.layout(size);
+    }
+
+    fn wrap_take_focus(&mut self, _: ::cursive::direction::Direction) -> bool {
+        true
+    }
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Key(Key::Enter) | Event::CtrlChar('e') => self.request_edition(),
+            _ => EventResult::Ignored,
+        }
+    }
+}
+
+// Hands terminal over to external program (`suspend`) and takes it back
+// (`resume`). Backend of `Cursive::default` is chosen by features in order:
+// blt, termion, crossterm, pancurses, ncurses.
+#[cfg(all(
+    feature = "pancurses-backend",
+    not(any(
+        feature = "blt-backend",
+        feature = "termion-backend",
+        feature = "crossterm-backend"
+    ))
+))]
+mod terminal {
+    use pancurses;
+
+    pub fn suspend() -> Result<(), String> {
+        pancurses::def_prog_mode();
+        pancurses::endwin();
+        Ok(())
+    }
+
+    pub fn resume() {
+        pancurses::reset_prog_mode();
+        pancurses::doupdate();
+    }
+}
+
+#[cfg(all(
+    feature = "ncurses-backend",
+    not(any(
+        feature = "blt-backend",
+        feature = "termion-backend",
+        feature = "crossterm-backend",
+        feature = "pancurses-backend"
+    ))
+))]
+mod terminal {
+    use ncurses;
+
+    pub fn suspend() -> Result<(), String> {
+        ncurses::def_prog_mode();
+        ncurses::endwin();
+        Ok(())
+    }
+
+    pub fn resume() {
+        ncurses::reset_prog_mode();
+        ncurses::refresh();
+    }
+}
+
+// termion & crossterm read terminal's input in background threads (which
+// would steal editor's keys) and blt has no terminal
+#[cfg(any(
+    feature = "blt-backend",
+    feature = "termion-backend",
+    feature = "crossterm-backend",
+    not(any(feature = "pancurses-backend", feature = "ncurses-backend"))
+))]
+mod terminal {
+    use messages::Message;
+
+    fn backend() -> &'static str {
+        if cfg!(feature = "blt-backend") {
+            "blt"
+        } else if cfg!(feature = "termion-backend") {
+            "termion"
+        } else if cfg!(feature = "crossterm-backend") {
+            "crossterm"
+        } else {
+            "dummy"
+        }
+    }
+
+    pub fn suspend() -> Result<(), String> {
+        let msg = Message::new("editor.unsupported_backend").param("backend", backend());
+        Err(msg.to_string())
+    }
+
+    pub fn resume() {}
+}
+
+/// Finds command of user's editor.
+fn editor_cmd() -> Vec<String> {
+    let cmd = env::var("VISUAL")
+        .ok()
+        .into_iter()
+        .chain(env::var("EDITOR").ok())
+        .find(|x| !x.trim().is_empty())
+        .unwrap_or("vi".to_string());
+    cmd.split_whitespace().map(|x| x.to_string()).collect()
+}
+
+fn temp_file(extension: &str) -> PathBuf {
+    let mut name = format!(
+        "fui-{}-{}",
+        process::id(),
+        NEXT_FILE.fetch_add(1, Ordering::SeqCst)
+    );
+    if !extension.is_empty() {
+        name = format!("{}.{}", name, extension.trim_start_matches('.'));
+    }
+    env::temp_dir().join(name)
+}
+
+/// Opens a new file readable only by user, it fails if `path` exists
+/// (even as a symlink).
+#[cfg(unix)]
+fn create_new(path: &Path) -> io::Result<fs::File> {
+    use std::os::unix::fs::OpenOptionsExt;
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)
+}
+
+#[cfg(not(unix))]
+fn create_new(path: &Path) -> io::Result<fs::File> {
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+}
+
+/// Writes `value` to a new temporary file, names taken by others are skipped.
+fn write_temp_file(value: &str, extension: &str) -> io::Result<PathBuf> {
+    loop {
+        let path = temp_file(extension);
+        let mut file = match create_new(&path) {
+            Ok(file) => file,
+            Err(ref e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        };
+        if let Err(e) = file.write_all(value.as_bytes()) {
+            let _ = fs::remove_file(&path);
+            return Err(e);
+        }
+        return Ok(path);
+    }
+}
+
+/// Opens `value` in external editor and returns the edited one.
+fn edit(value: &str, extension: &str) -> Result<String, String> {
+    let path = write_temp_file(value, extension).map_err(|e| {
+        let msg = Message::new("editor.create_failed")
+            .param("path", format!("{:?}", env::temp_dir()))
+            .param("error", e);
+        msg.to_string()
+    })?;
+    let result = run_editor(&editor_cmd(), &path);
+    let _ = fs::remove_file(&path);
+    result
+}
+
+fn run_editor(cmd: &[String], path: &Path) -> Result<String, String> {
+    let (program, args) = match cmd.split_first() {
+        Some(v) => v,
+        None => return Err(messages::get("editor.not_set")),
+    };
+    let status = process::Command::new(program)
+        .args(args)
+        .arg(path)
+        .status()
+        .map_err(|e| {
+            let msg = Message::new("editor.run_failed")
+                .param("program", format!("{:?}", program))
+                .param("error", e);
+            msg.to_string()
+        })?;
+    if !status.success() {
+        let msg = Message::new("editor.exit_status")
+            .param("program", format!("{:?}", program))
+            .param("status", status);
+        return Err(msg.to_string());
+    }
+    fs::read_to_string(path).map_err(|e| {
+        let msg = Message::new("editor.read_failed")
+            .param("path", format!("{:?}", path))
+            .param("error", e);
+        msg.to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_utils::TempDir;
+
+    #[test]
+    fn summary_shows_first_line_and_count_of_others() {
+        assert_eq!(summarize(""), "<empty> (Enter: edit)");
+        assert_eq!(summarize("a: 1\n"), "a: 1 (Enter: edit)");
+        assert_eq!(
+            summarize("a: 1\nb: 2\nc: 3"),
+            "a: 1 (+2 lines, Enter: edit)"
+        );
+    }
+
+    #[test]
+    fn temp_file_has_extension() {
+        let path = temp_file(".yaml");
+        assert_eq!(path.extension().unwrap(), "yaml");
+        assert!(temp_file("").extension().is_none());
+    }
+
+    #[test]
+    fn temp_file_is_written_only_by_user() {
+        let path = write_temp_file("text", "txt").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "text");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let _ = fs::remove_file(&path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn existing_symlink_is_not_written_through() {
+        let dir = TempDir::new("editor").file("target", "keep");
+        let link = dir.join("link");
+        ::std::os::unix::fs::symlink(dir.join("target"), &link).unwrap();
+        let err = create_new(&link).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(fs::read_to_string(dir.join("target")).unwrap(), "keep");
+    }
+
+    #[test]
+    fn editor_result_is_read_from_file() {
+        let path = temp_file("txt");
+        fs::write(&path, "old").unwrap();
+        let cmd = vec![
+            "sed".to_string(),
+            "-i".to_string(),
+            "s/old/new/".to_string(),
+        ];
+        assert_eq!(run_editor(&cmd, &path), Ok("new".to_string()));
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn failing_editor_is_error() {
+        let path = temp_file("txt");
+        let cmd = vec!["false".to_string()];
+        assert!(run_editor(&cmd, &path).is_err());
+    }
+}
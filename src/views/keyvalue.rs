@@ -0,0 +1,193 @@
+use cursive::event::{Event, EventResult, Key};
+use cursive::traits::{Boxable, View};
+use cursive::view::ViewWrapper;
+use cursive::views::{BoxView, EditView, LinearLayout, Panel, SelectView, TextView};
+
+const EDIT_IDX: usize = 0;
+const PAIRS_IDX: usize = 1;
+const KEY_IDX: usize = 0;
+const VALUE_IDX: usize = 2;
+
+/// Editable list of key/value pairs.
+///
+/// * `Enter` on edited pair adds it to the list
+/// * `Enter` on listed pair moves it back to edition
+/// * `Delete` on listed pair removes it
+pub struct KeyValue {
+    view: LinearLayout,
+}
+
+impl KeyValue {
+    /// Creates a new, empty `KeyValue`.
+    pub fn new() -> Self {
+        let edit_row = LinearLayout::horizontal()
+            .child(EditView::new().full_width())
+            .child(TextView::new(" = "))
+            .child(EditView::new().full_width());
+        let layout = LinearLayout::vertical()
+            .child(edit_row)
+            .child(Panel::new(SelectView::<(String, String)>::new()));
+        KeyValue { view: layout }
+    }
+
+    fn get_edit_row(&self) -> &LinearLayout {
+        self.view
+            .get_child(EDIT_IDX)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<LinearLayout>()
+            .unwrap()
+    }
+
+    fn get_edit_row_mut(&mut self) -> &mut LinearLayout {
+        self.view
+            .get_child_mut(EDIT_IDX)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<LinearLayout>()
+            .unwrap()
+    }
+
+    fn get_edit(&self, idx: usize) -> &EditView {
+        self.get_edit_row()
+            .get_child(idx)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<BoxView<EditView>>()
+            .unwrap()
+            .get_inner()
+    }
+
+    fn get_edit_mut(&mut self, idx: usize) -> &mut EditView {
+        self.get_edit_row_mut()
+            .get_child_mut(idx)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<BoxView<EditView>>()
+            .unwrap()
+            .get_inner_mut()
+    }
+
+    fn get_pairs_view(&self) -> &SelectView<(String, String)> {
+        self.view
+            .get_child(PAIRS_IDX)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Panel<SelectView<(String, String)>>>()
+            .unwrap()
+            .get_inner()
+    }
+
+    fn get_pairs_view_mut(&mut self) -> &mut SelectView<(String, String)> {
+        self.view
+            .get_child_mut(PAIRS_IDX)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<Panel<SelectView<(String, String)>>>()
+            .unwrap()
+            .get_inner_mut()
+    }
+
+    /// Returns listed pairs.
+    pub fn get_pairs(&self) -> Vec<(String, String)> {
+        self.get_pairs_view()
+            .iter()
+            .map(|(_, pair)| pair.clone())
+            .collect()
+    }
+
+    /// Appends `pairs` to the list.
+    pub fn add_pairs(&mut self, pairs: Vec<(String, String)>) {
+        let select = self.get_pairs_view_mut();
+        for (key, value) in pairs.into_iter() {
+            select.add_item(format!("{}={}", key, value), (key, value));
+        }
+    }
+
+    /// Appends `pairs` to the list.
+    ///
+    /// Chainable variant.
+    pub fn pairs(mut self, pairs: Vec<(String, String)>) -> Self {
+        self.add_pairs(pairs);
+        self
+    }
+
+    /// Moves edited pair to the list.
+    fn add_edited(&mut self) {
+        let key = self.get_edit(KEY_IDX).get_content();
+        if key.is_empty() {
+            return;
+        }
+        let value = self.get_edit(VALUE_IDX).get_content();
+        self.add_pairs(vec![((*key).clone(), (*value).clone())]);
+        self.get_edit_mut(KEY_IDX).set_content("");
+        self.get_edit_mut(VALUE_IDX).set_content("");
+        self.focus_key();
+    }
+
+    /// Moves selected pair from the list to edition.
+    fn edit_selected(&mut self) {
+        if let Some((key, value)) = self.remove_selected() {
+            self.get_edit_mut(KEY_IDX).set_content(key);
+            self.get_edit_mut(VALUE_IDX).set_content(value);
+            self.focus_key();
+        }
+    }
+
+    fn remove_selected(&mut self) -> Option<(String, String)> {
+        let select = self.get_pairs_view_mut();
+        if let Some(idx) = select.selected_id() {
+            let pair = select.selection().map(|x| (*x).clone());
+            select.remove_item(idx);
+            pair
+        } else {
+            None
+        }
+    }
+
+    fn is_key_focused(&self) -> bool {
+        self.view.get_focus_index() == EDIT_IDX && self.get_edit_row().get_focus_index() == KEY_IDX
+    }
+
+    fn focus_key(&mut self) {
+        // at most: list -> value -> key
+        for _ in 0..2 {
+            if self.is_key_focused() {
+                break;
+            }
+            let tab_event = Event::Shift(Key::Tab);
+            self.with_view_mut(|v| v.on_event(tab_event));
+        }
+    }
+}
+
+impl Default for KeyValue {
+    fn default() -> Self {
+        KeyValue::new()
+    }
+}
+
+impl ViewWrapper for KeyValue {
+    wrap_impl!(self.view: LinearLayout);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        let focused = self.view.get_focus_index();
+        match event {
+            Event::Key(Key::Enter) if focused == EDIT_IDX => {
+                self.add_edited();
+                EventResult::Consumed(None)
+            }
+            Event::Key(Key::Enter) if focused == PAIRS_IDX => {
+                self.edit_selected();
+                EventResult::Consumed(None)
+            }
+            Event::Key(Key::Del) if focused == PAIRS_IDX => {
+                self.remove_selected();
+                EventResult::Consumed(None)
+            }
+            _ => self
+                .with_view_mut(|v| v.on_event(event))
+                .unwrap_or(EventResult::Ignored),
+        }
+    }
+}
@@ -5,10 +5,16 @@
 //! [Views]: ../views/index.html
 //! [Cursive]: ../../cursive/views/index.html
 mod autocomplete;
+mod editor;
+mod keyvalue;
 mod multiselect;
+mod path_browser;
 
 pub use self::autocomplete::Autocomplete;
+pub use self::editor::Editor;
+pub use self::keyvalue::KeyValue;
 pub use self::multiselect::Multiselect;
+pub use self::path_browser::PathBrowser;
 
 use cursive::views::SelectView;
 
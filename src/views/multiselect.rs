@@ -75,6 +75,22 @@ impl Multiselect {
         box_view.get_inner().get_inner()
     }
 
+    fn get_options_view_mut(&mut self) -> &mut Autocomplete {
+        let box_view = self
+            .view
+            .get_child_mut(self.options_idx as usize)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<Panel<BoxView<Autocomplete>>>()
+            .unwrap();
+        box_view.get_inner_mut().get_inner_mut()
+    }
+
+    /// Queries feeder again (like after its data changed).
+    pub fn refresh(&mut self) {
+        self.get_options_view_mut().refresh();
+    }
+
     fn get_selected_view(&self) -> &SelectView<String> {
         let box_view = self
             .view
@@ -0,0 +1,423 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use cursive::event::{Callback, Event, EventResult, Key};
+use cursive::traits::{Boxable, View};
+use cursive::view::ViewWrapper;
+use cursive::views::{BoxView, LinearLayout, SelectView, TextView};
+use cursive::Cursive;
+use cursive::With;
+
+use messages;
+
+type OnSubmit = Option<Rc<dyn Fn(&mut Cursive, Vec<String>)>>;
+
+/// Browser of file system tree.
+///
+/// * `Right` expands dir
+/// * `Left` collapses dir (or moves to its parent)
+/// * `Backspace` changes root to parent dir
+/// * `.` toggles hidden files
+/// * `Space` marks item (if multiple selection is allowed)
+/// * `Enter` submits selection
+pub struct PathBrowser {
+    view: LinearLayout,
+    root: PathBuf,
+    expanded: HashSet<PathBuf>,
+    marked: Vec<PathBuf>,
+    show_hidden: bool,
+    dirs_only: bool,
+    multiple: bool,
+    extensions: Vec<String>,
+    on_submit: OnSubmit,
+}
+
+impl PathBrowser {
+    /// Creates a new `PathBrowser` showing tree of `root` dir.
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        let layout = LinearLayout::vertical()
+            .child(TextView::new(""))
+            .child(SelectView::<PathBuf>::new().full_height())
+            .child(TextView::new(messages::get("path_browser.hints")));
+        let mut browser = PathBrowser {
+            view: layout,
+            root: root.as_ref().to_path_buf(),
+            expanded: HashSet::new(),
+            marked: Vec::new(),
+            show_hidden: false,
+            dirs_only: false,
+            multiple: false,
+            extensions: Vec::new(),
+            on_submit: None,
+        };
+        browser.refresh();
+        browser
+    }
+
+    /// Shows hidden files (starting with `.`).
+    pub fn show_hidden(mut self, show: bool) -> Self {
+        self.show_hidden = show;
+        self.refresh();
+        self
+    }
+
+    /// Shows only dirs.
+    pub fn dirs_only(mut self) -> Self {
+        self.dirs_only = true;
+        self.refresh();
+        self
+    }
+
+    /// Shows only files with given `extensions` (dirs are always shown).
+    pub fn extensions<S: Into<String>>(mut self, extensions: Vec<S>) -> Self {
+        self.extensions = extensions.into_iter().map(|x| x.into()).collect();
+        self.refresh();
+        self
+    }
+
+    /// Allows marking many items.
+    pub fn multiple(mut self) -> Self {
+        self.multiple = true;
+        let hints = messages::get("path_browser.hints_multiple");
+        self.get_hints_mut().set_content(hints);
+        self
+    }
+
+    /// Sets the function to be called when selection is submitted.
+    pub fn set_on_submit<F>(&mut self, callback: F)
+    where
+        F: Fn(&mut Cursive, Vec<String>) + 'static,
+    {
+        self.on_submit = Some(Rc::new(callback));
+    }
+
+    /// Sets the function to be called when selection is submitted.
+    ///
+    /// Chainable variant.
+    pub fn on_submit<F>(self, callback: F) -> Self
+    where
+        F: Fn(&mut Cursive, Vec<String>) + 'static,
+    {
+        self.with(|v| v.set_on_submit(callback))
+    }
+
+    /// Returns selected paths.
+    ///
+    /// These are marked items or the current one if nothing is marked.
+    pub fn get_selection(&self) -> Vec<String> {
+        let paths = if !self.marked.is_empty() {
+            self.marked.clone()
+        } else {
+            self.get_select_view()
+                .selection()
+                .map(|x| vec![(*x).clone()])
+                .unwrap_or_default()
+        };
+        paths.iter().map(|x| format!("{}", x.display())).collect()
+    }
+
+    fn get_hints_mut(&mut self) -> &mut TextView {
+        self.view
+            .get_child_mut(2)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<TextView>()
+            .unwrap()
+    }
+
+    fn get_select_view(&self) -> &SelectView<PathBuf> {
+        self.view
+            .get_child(1)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<BoxView<SelectView<PathBuf>>>()
+            .unwrap()
+            .get_inner()
+    }
+
+    fn get_select_view_mut(&mut self) -> &mut SelectView<PathBuf> {
+        self.view
+            .get_child_mut(1)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<BoxView<SelectView<PathBuf>>>()
+            .unwrap()
+            .get_inner_mut()
+    }
+
+    fn is_visible(&self, path: &Path, is_dir: bool) -> bool {
+        let name = path
+            .file_name()
+            .map(|x| x.to_string_lossy().into_owned())
+            .unwrap_or("".to_string());
+        if !self.show_hidden && name.starts_with('.') {
+            return false;
+        }
+        if is_dir {
+            return true;
+        }
+        if self.dirs_only {
+            return false;
+        }
+        if self.extensions.is_empty() {
+            return true;
+        }
+        let ext = path
+            .extension()
+            .map(|x| x.to_string_lossy().into_owned())
+            .unwrap_or("".to_string());
+        self.extensions.contains(&ext)
+    }
+
+    /// Lists visible children of `dir`, dirs first.
+    fn list_dir(&self, dir: &Path) -> Vec<(PathBuf, bool)> {
+        let entries = match fs::read_dir(dir) {
+            Ok(v) => v,
+            Err(_) => return Vec::new(),
+        };
+        let mut items = entries
+            .filter_map(|x| x.ok())
+            .map(|x| {
+                let path = x.path();
+                let is_dir = path.is_dir();
+                (path, is_dir)
+            })
+            .filter(|&(ref path, is_dir)| self.is_visible(path, is_dir))
+            .collect::<Vec<(PathBuf, bool)>>();
+        items.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        items
+    }
+
+    fn add_rows(&self, dir: &Path, depth: usize, rows: &mut Vec<(String, PathBuf)>) {
+        for (path, is_dir) in self.list_dir(dir) {
+            let name = path
+                .file_name()
+                .map(|x| x.to_string_lossy().into_owned())
+                .unwrap_or("".to_string());
+            let expanded = self.expanded.contains(&path);
+            let marker = match (is_dir, expanded) {
+                (true, true) => "▾ ",
+                (true, false) => "▸ ",
+                (false, _) => "  ",
+            };
+            let mark = if !self.multiple {
+                ""
+            } else if self.marked.contains(&path) {
+                "[x] "
+            } else {
+                "[ ] "
+            };
+            let slash = if is_dir { "/" } else { "" };
+            let label = format!("{}{}{}{}{}", "  ".repeat(depth), mark, marker, name, slash);
+            rows.push((label, path.clone()));
+            if is_dir && expanded {
+                self.add_rows(&path, depth + 1, rows);
+            }
+        }
+    }
+
+    /// Rebuilds tree keeping selected row.
+    fn refresh(&mut self) {
+        let current = self.get_select_view().selection();
+        let mut rows = Vec::new();
+        let root = self.root.clone();
+        self.add_rows(&root, 0, &mut rows);
+        let header = format!("{}", self.root.display());
+        self.view
+            .get_child_mut(0)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<TextView>()
+            .unwrap()
+            .set_content(header);
+        let select = self.get_select_view_mut();
+        select.clear();
+        let mut selected = 0;
+        for (idx, (label, path)) in rows.into_iter().enumerate() {
+            if current.as_ref().map(|x| **x == path).unwrap_or(false) {
+                selected = idx;
+            }
+            select.add_item(label, path);
+        }
+        if !select.is_empty() {
+            select.set_selection(selected);
+        }
+    }
+
+    fn expand(&mut self) {
+        if let Some(path) = self.get_select_view().selection() {
+            if path.is_dir() {
+                self.expanded.insert((*path).clone());
+                self.refresh();
+            }
+        }
+    }
+
+    fn collapse(&mut self) {
+        if let Some(path) = self.get_select_view().selection() {
+            if self.expanded.remove(&*path) {
+                self.refresh();
+            } else if let Some(parent) = path.parent() {
+                // move to parent row
+                let select = self.get_select_view_mut();
+                let idx = select.iter().position(|(_, p)| p == parent);
+                if let Some(idx) = idx {
+                    select.set_selection(idx);
+                }
+            }
+        }
+    }
+
+    fn go_up(&mut self) {
+        let parent = fs::canonicalize(&self.root)
+            .ok()
+            .and_then(|x| x.parent().map(|p| p.to_path_buf()));
+        if let Some(parent) = parent {
+            self.root = parent;
+            self.refresh();
+        }
+    }
+
+    fn toggle_mark(&mut self) {
+        if let Some(path) = self.get_select_view().selection() {
+            if let Some(idx) = self.marked.iter().position(|x| *x == *path) {
+                self.marked.remove(idx);
+            } else {
+                self.marked.push((*path).clone());
+            }
+            self.refresh();
+        }
+    }
+}
+
+impl ViewWrapper for PathBrowser {
+    wrap_impl!(self.view: LinearLayout);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Key(Key::Right) => {
+                self.expand();
+                EventResult::Consumed(None)
+            }
+            Event::Key(Key::Left) => {
+                self.collapse();
+                EventResult::Consumed(None)
+            }
+            Event::Key(Key::Backspace) => {
+                self.go_up();
+                EventResult::Consumed(None)
+            }
+            Event::Char('.') => {
+                self.show_hidden = !self.show_hidden;
+                self.refresh();
+                EventResult::Consumed(None)
+            }
+            Event::Char(' ') if self.multiple => {
+                self.toggle_mark();
+                EventResult::Consumed(None)
+            }
+            Event::Key(Key::Enter) => {
+                let selection = self.get_selection();
+                let cb = self
+                    .on_submit
+                    .clone()
+                    .map(|cb| Callback::from_fn(move |c| cb(c, selection.clone())));
+                EventResult::Consumed(cb)
+            }
+            _ => self
+                .with_view_mut(|v| v.on_event(event))
+                .unwrap_or(EventResult::Ignored),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_utils::TempDir;
+
+    fn labels(browser: &PathBrowser) -> Vec<String> {
+        browser
+            .get_select_view()
+            .iter()
+            .map(|(label, _)| label.to_string())
+            .collect()
+    }
+
+    fn tree() -> TempDir {
+        TempDir::new("path-browser")
+            .dirs(&["b_dir/nested", "a_dir"])
+            .file("a_dir/inner.rs", "")
+            .file("lib.rs", "")
+            .file("Cargo.toml", "")
+            .file("README.md", "")
+            .file(".hidden", "")
+    }
+
+    #[test]
+    fn dirs_are_listed_first_with_trailing_slash() {
+        let dir = tree();
+        let browser = PathBrowser::new(&*dir);
+        assert_eq!(
+            labels(&browser),
+            vec![
+                "▸ a_dir/",
+                "▸ b_dir/",
+                "  Cargo.toml",
+                "  README.md",
+                "  lib.rs"
+            ]
+        );
+    }
+
+    #[test]
+    fn hidden_files_are_toggled() {
+        let dir = tree();
+        let mut browser = PathBrowser::new(&*dir);
+        assert!(!labels(&browser).contains(&"  .hidden".to_string()));
+        browser.on_event(Event::Char('.'));
+        assert!(labels(&browser).contains(&"  .hidden".to_string()));
+    }
+
+    #[test]
+    fn extensions_filter_files() {
+        let dir = tree();
+        let browser = PathBrowser::new(&*dir).extensions(vec!["toml"]);
+        let found = labels(&browser);
+        assert!(found.contains(&"  Cargo.toml".to_string()));
+        assert!(!found.contains(&"  README.md".to_string()));
+    }
+
+    #[test]
+    fn dir_is_expanded_and_collapsed() {
+        let dir = tree();
+        let mut browser = PathBrowser::new(&*dir);
+        browser.expand();
+        assert!(labels(&browser).contains(&"    inner.rs".to_string()));
+        browser.collapse();
+        assert!(!labels(&browser).contains(&"    inner.rs".to_string()));
+    }
+
+    #[test]
+    fn marked_items_are_selection() {
+        let dir = tree();
+        let mut browser = PathBrowser::new(&*dir).multiple();
+        browser.toggle_mark();
+        browser.get_select_view_mut().set_selection(1);
+        browser.toggle_mark();
+        let exp = vec![
+            dir.join("a_dir").display().to_string(),
+            dir.join("b_dir").display().to_string(),
+        ];
+        assert_eq!(browser.get_selection(), exp);
+    }
+
+    #[test]
+    fn missing_dir_is_empty() {
+        let dir = tree();
+        let browser = PathBrowser::new(dir.join("missing-dir"));
+        assert_eq!(labels(&browser), Vec::<String>::new());
+    }
+}